@@ -0,0 +1,250 @@
+//! Command-line parsing (no dependencies; the surface is small).
+
+use std::path::PathBuf;
+
+use nsc_arbiter_core::{calibrate, CalibrationGrid, Objective};
+
+use crate::CliError;
+
+pub const USAGE: &str = "\
+usage: nsc_arbiter replay [options]
+       nsc_arbiter calibrate [options]
+
+replay: read SignalEvents as JSONL, run them through the supervisor, write ActionEvents
+as JSONL.
+
+options:
+  --input FILE          events to read (default: stdin; `-` also means stdin)
+  --output FILE         actions to write (default: stdout)
+  --cfg FILE            ArbiterCfg as JSON; missing fields take their defaults
+  --snapshot FILE       start from a snapshot (SupervisorSnapshotV2 or state-only JSON)
+  --save-snapshot FILE  write a SupervisorSnapshotV2 JSON after the run
+  --tick-field NAME     one tick per run of equal NAME values; numeric values are the
+                        logical time (`ingest_at`), others advance the clock by one
+  --batch N             one tick per N events (default: 1)
+  --shards N            supervisor shards (default: 1)
+  --trace               attach decision traces to actions
+
+calibrate: search tau_* thresholds against labelled events and print the chosen
+ArbiterCfg as JSON. A tick should escalate for an intent if any of its events has
+a true label.
+
+options:
+  --input, --output, --cfg, --tick-field, --batch   as for replay (--cfg is the base)
+  --label-field NAME    boolean label on each event (default: label)
+  --objective OBJ       f1 (default), precision@RECALL or recall@RATE, e.g. recall@0.05
+  --report FILE         write the full report (frontier, chosen point) as JSON
+  --tau-e VALUES        candidates as FROM:TO:STEP or a comma list; likewise
+  --tau-s VALUES        for each threshold. Defaults to a grid around the
+  --tau-gate VALUES     built-in defaults.
+  --tau-rep VALUES
+  --tau-stall VALUES
+";
+
+/// How input events are grouped into ingest batches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// Consecutive events with equal values of this field form one tick.
+    Field(String),
+    /// Fixed-size batches.
+    Batch(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayArgs {
+    pub input: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub cfg: Option<PathBuf>,
+    pub snapshot: Option<PathBuf>,
+    pub save_snapshot: Option<PathBuf>,
+    pub grouping: Grouping,
+    pub shards: Option<usize>,
+    pub trace: bool,
+}
+
+impl Default for ReplayArgs {
+    fn default() -> Self {
+        Self {
+            input: None,
+            output: None,
+            cfg: None,
+            snapshot: None,
+            save_snapshot: None,
+            grouping: Grouping::Batch(1),
+            shards: None,
+            trace: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrateArgs {
+    pub input: Option<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub cfg: Option<PathBuf>,
+    pub report: Option<PathBuf>,
+    pub grouping: Grouping,
+    pub label_field: String,
+    pub objective: Objective,
+    pub grid: CalibrationGrid,
+}
+
+impl Default for CalibrateArgs {
+    fn default() -> Self {
+        Self {
+            input: None,
+            output: None,
+            cfg: None,
+            report: None,
+            grouping: Grouping::Batch(1),
+            label_field: "label".to_string(),
+            objective: Objective::default(),
+            grid: CalibrationGrid::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Help,
+    Replay(ReplayArgs),
+    Calibrate(CalibrateArgs),
+}
+
+/// Parse arguments (without the program name).
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut it = args.into_iter();
+    let cmd = match it.next() {
+        Some(cmd) => cmd,
+        None => return Err(CliError::Usage("missing command".to_string())),
+    };
+    match cmd.as_str() {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "replay" => parse_replay(it).map(Command::Replay),
+        "calibrate" => parse_calibrate(it).map(Command::Calibrate),
+        other => Err(CliError::Usage(format!("unknown command `{other}`"))),
+    }
+}
+
+fn parse_replay<I: Iterator<Item = String>>(mut it: I) -> Result<ReplayArgs, CliError> {
+    let mut out = ReplayArgs::default();
+    let mut grouping_set = false;
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().ok_or_else(|| CliError::Usage(format!("{flag} needs a value")));
+        match flag.as_str() {
+            "--input" => {
+                let v = value()?;
+                out.input = if v == "-" { None } else { Some(v.into()) };
+            }
+            "--output" => out.output = Some(value()?.into()),
+            "--cfg" => out.cfg = Some(value()?.into()),
+            "--snapshot" => out.snapshot = Some(value()?.into()),
+            "--save-snapshot" => out.save_snapshot = Some(value()?.into()),
+            "--tick-field" | "--batch" => {
+                let v = value()?;
+                set_grouping(&mut out.grouping, &mut grouping_set, &flag, v)?;
+            }
+            "--shards" => {
+                let v = value()?;
+                out.shards = Some(positive(&flag, &v)?);
+            }
+            "--trace" => out.trace = true,
+            "-h" | "--help" => return Err(CliError::Usage(String::new())),
+            other => return Err(CliError::Usage(format!("unknown option `{other}`"))),
+        }
+    }
+    Ok(out)
+}
+
+fn parse_calibrate<I: Iterator<Item = String>>(mut it: I) -> Result<CalibrateArgs, CliError> {
+    let mut out = CalibrateArgs::default();
+    let mut grouping_set = false;
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().ok_or_else(|| CliError::Usage(format!("{flag} needs a value")));
+        match flag.as_str() {
+            "--input" => {
+                let v = value()?;
+                out.input = if v == "-" { None } else { Some(v.into()) };
+            }
+            "--output" => out.output = Some(value()?.into()),
+            "--cfg" => out.cfg = Some(value()?.into()),
+            "--report" => out.report = Some(value()?.into()),
+            "--tick-field" | "--batch" => {
+                let v = value()?;
+                set_grouping(&mut out.grouping, &mut grouping_set, &flag, v)?;
+            }
+            "--label-field" => out.label_field = value()?,
+            "--objective" => {
+                let v = value()?;
+                out.objective = parse_objective(&v)?;
+            }
+            "--tau-e" => out.grid.tau_e = parse_values(&flag, &value()?)?,
+            "--tau-s" => out.grid.tau_s = parse_values(&flag, &value()?)?,
+            "--tau-gate" => out.grid.tau_gate = parse_values(&flag, &value()?)?,
+            "--tau-rep" => out.grid.tau_rep = counts(&flag, &value()?)?,
+            "--tau-stall" => out.grid.tau_stall = counts(&flag, &value()?)?,
+            "-h" | "--help" => return Err(CliError::Usage(String::new())),
+            other => return Err(CliError::Usage(format!("unknown option `{other}`"))),
+        }
+    }
+    Ok(out)
+}
+
+fn parse_objective(v: &str) -> Result<Objective, CliError> {
+    let bad = || CliError::Usage(format!("--objective expects f1, precision@RECALL or recall@RATE, got `{v}`"));
+    let (name, arg) = match v.split_once('@') {
+        Some((name, arg)) => (name, Some(arg.parse::<f32>().map_err(|_| bad())?)),
+        None => (v, None),
+    };
+    match (name, arg) {
+        ("f1", None) => Ok(Objective::F1),
+        ("precision", Some(r)) => Ok(Objective::PrecisionAtRecall(r)),
+        ("recall", Some(rate)) => Ok(Objective::RecallAtRate(rate)),
+        _ => Err(bad()),
+    }
+}
+
+/// `FROM:TO:STEP` or `a,b,c`.
+fn parse_values(flag: &str, v: &str) -> Result<Vec<f32>, CliError> {
+    let bad = || CliError::Usage(format!("{flag} expects FROM:TO:STEP or a comma list, got `{v}`"));
+    let nums: Vec<f32> = v
+        .split([':', ','])
+        .map(|x| x.trim().parse::<f32>().map_err(|_| bad()))
+        .collect::<Result<_, _>>()?;
+    if v.contains(':') {
+        match nums[..] {
+            [from, to, step] if step > 0.0 && from <= to => Ok(calibrate::steps(from, to, step)),
+            _ => Err(bad()),
+        }
+    } else {
+        Ok(nums)
+    }
+}
+
+fn counts(flag: &str, v: &str) -> Result<Vec<u32>, CliError> {
+    parse_values(flag, v)?
+        .into_iter()
+        .map(|x| {
+            if x >= 1.0 && x.fract() == 0.0 {
+                Ok(x as u32)
+            } else {
+                Err(CliError::Usage(format!("{flag} expects positive integers, got `{v}`")))
+            }
+        })
+        .collect()
+}
+
+fn set_grouping(grouping: &mut Grouping, set: &mut bool, flag: &str, v: String) -> Result<(), CliError> {
+    if std::mem::replace(set, true) {
+        return Err(CliError::Usage("--tick-field and --batch are exclusive".to_string()));
+    }
+    *grouping = if flag == "--batch" { Grouping::Batch(positive(flag, &v)?) } else { Grouping::Field(v) };
+    Ok(())
+}
+
+fn positive(flag: &str, v: &str) -> Result<usize, CliError> {
+    match v.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(CliError::Usage(format!("{flag} expects a positive integer, got `{v}`"))),
+    }
+}
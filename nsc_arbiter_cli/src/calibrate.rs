@@ -0,0 +1,114 @@
+//! `nsc_arbiter calibrate`: labelled JSONL events in, calibrated `ArbiterCfg` out.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+
+use nsc_arbiter_core::{
+    calibrate, freeze_flags_cfg, union_flags, ArbiterCfg, ArbiterEvidenceView, CalibrationReport, FreezeCfg, FreezeFlags, LabelledSequence,
+    LabelledTick, TextMemory,
+};
+use nsc_arbiter_supervisor::{build_evidence_batch, BasicEvidenceBuilder, SignalEvent};
+
+use crate::args::{CalibrateArgs, Grouping};
+use crate::records::EventRecord;
+use crate::replay::{open_input, open_output, read_to_string};
+use crate::ticks::read_ticks;
+use crate::CliError;
+
+/// Run `calibrate` with file / stdio handling, per `args`.
+pub fn run(args: &CalibrateArgs) -> Result<CalibrationReport, CliError> {
+    let base: ArbiterCfg = match &args.cfg {
+        Some(path) => serde_json::from_str(&read_to_string(path)?)
+            .map_err(|err| CliError::Json { what: "cfg".to_string(), line: 0, err })?,
+        None => ArbiterCfg::default(),
+    };
+    let input = open_input(args.input.as_deref())?;
+    let data = labelled_sequences(input, &args.grouping, &args.label_field, &base.freeze)?;
+    let report = calibrate(&base, &data, &args.grid, args.objective);
+
+    if let Some(path) = &args.report {
+        let mut w = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut w, &report)
+            .map_err(|err| CliError::Json { what: "report".to_string(), line: 0, err })?;
+        w.write_all(b"\n")?;
+        w.flush()?;
+    }
+    if let Some(cfg) = &report.cfg {
+        let mut w = open_output(args.output.as_deref())?;
+        serde_json::to_writer_pretty(&mut w, cfg)
+            .map_err(|err| CliError::Json { what: "output".to_string(), line: 0, err })?;
+        w.write_all(b"\n")?;
+        w.flush()?;
+    }
+    Ok(report)
+}
+
+/// Group labelled events into per-intent tick sequences, the way the supervisor sees
+/// them: one evidence view per intent per tick, freeze flags (detected per `freeze`)
+/// per source, OR-ed over that source's texts, `cross_tick_repeat` from a per-source
+/// `TextMemory`. Source profiles, freeze eligibility included, are not applied.
+pub fn labelled_sequences<R: BufRead>(
+    input: R,
+    grouping: &Grouping,
+    label_field: &str,
+    freeze: &FreezeCfg,
+) -> Result<Vec<LabelledSequence>, CliError> {
+    let builder = BasicEvidenceBuilder::default();
+    let mut sequences: BTreeMap<String, LabelledSequence> = BTreeMap::new();
+    let mut memories: HashMap<(String, String), TextMemory> = HashMap::new();
+    let mut tick_no = 0;
+
+    read_ticks(input, grouping, |tick, _key| {
+        tick_no += 1;
+        let mut labels: HashMap<&str, bool> = HashMap::new();
+        let mut ff: HashMap<&str, BTreeMap<&str, FreezeFlags>> = HashMap::new();
+        let mut texts: HashMap<&str, BTreeMap<&str, Vec<&str>>> = HashMap::new();
+        for ev in tick {
+            let label = match ev.extra.get(label_field).and_then(|v| v.as_bool()) {
+                Some(label) => label,
+                None => {
+                    return Err(CliError::Input {
+                        line: 0,
+                        msg: format!("tick {tick_no}: event for `{}` has no boolean `{label_field}`", ev.intent_id),
+                    })
+                }
+            };
+            *labels.entry(ev.intent_id.as_str()).or_default() |= label;
+            if let Some(text) = &ev.text {
+                let f = freeze_flags_cfg(text, freeze);
+                let e = ff.entry(ev.intent_id.as_str()).or_default().entry(ev.source_id.as_str()).or_default();
+                *e = e.union(f);
+                let t = texts.entry(ev.intent_id.as_str()).or_default();
+                t.entry(ev.source_id.as_str()).or_default().push(text);
+            }
+        }
+
+        let events: Vec<SignalEvent<'_>> = tick.iter().map(EventRecord::to_signal).collect();
+        let mut views: BTreeMap<String, ArbiterEvidenceView> = BTreeMap::new();
+        for e in build_evidence_batch(&builder, &events) {
+            views
+                .entry(e.intent_id.clone())
+                .or_insert_with(|| ArbiterEvidenceView::new(e.intent_id.clone()))
+                .push(e);
+        }
+        for (intent_id, view) in views {
+            let mut source_flags: Vec<(String, FreezeFlags)> = Vec::new();
+            for (source_id, t) in texts.remove(intent_id.as_str()).unwrap_or_default() {
+                let mut f = ff[intent_id.as_str()][source_id];
+                let memory = memories.entry((intent_id.clone(), source_id.to_string())).or_default();
+                f.cross_tick_repeat = memory.observe(t, freeze);
+                source_flags.push((source_id.to_string(), f));
+            }
+            let tick = LabelledTick {
+                freeze_flags: union_flags(&source_flags),
+                source_flags,
+                should_escalate: labels.get(intent_id.as_str()).copied().unwrap_or(false),
+                view,
+            };
+            sequences.entry(intent_id).or_default().ticks.push(tick);
+        }
+        Ok(())
+    })?;
+    Ok(sequences.into_values().collect())
+}
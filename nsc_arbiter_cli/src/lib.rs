@@ -0,0 +1,62 @@
+//! nsc_arbiter_cli
+//!
+//! Offline driver for `nsc_arbiter_supervisor`, built as the `nsc_arbiter` binary.
+//!
+//! Responsibilities:
+//! - read `SignalEvent`s as JSONL (file or stdin)
+//! - group them into ticks (by a field, or fixed-size batches)
+//! - run them through `ArbiterSupervisor::ingest`
+//! - write `ActionEvent`s as JSONL
+//! - calibrate `tau_*` thresholds against labelled events
+//!
+//! Used to reproduce incidents and try threshold changes without a live system.
+//! All IO lives here; the core and supervisor crates stay IO-free.
+
+pub mod args;
+pub mod calibrate;
+pub mod records;
+pub mod replay;
+pub mod ticks;
+
+pub use args::{parse_args, CalibrateArgs, Command, Grouping, ReplayArgs, USAGE};
+pub use calibrate::labelled_sequences;
+pub use records::{ActionRecord, EventRecord};
+pub use replay::{run_replay, RunStats};
+
+/// CLI failure.
+#[derive(Debug)]
+pub enum CliError {
+    /// Bad command line; print `USAGE`.
+    Usage(String),
+    Io(std::io::Error),
+    /// Malformed JSON input. `line` is 1-based, 0 for whole-file inputs.
+    Json { what: String, line: usize, err: serde_json::Error },
+    /// Well-formed input the CLI cannot use. `line` is 1-based, 0 if not line-specific.
+    Input { line: usize, msg: String },
+    /// The run completed but produced no usable result.
+    NoResult(String),
+    Snapshot(nsc_arbiter_supervisor::SnapshotError),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(msg) => write!(f, "{msg}"),
+            CliError::Io(err) => write!(f, "io error: {err}"),
+            CliError::Json { what, line: 0, err } => write!(f, "{what}: {err}"),
+            CliError::Json { what, line, err } => write!(f, "{what} line {line}: {err}"),
+            CliError::Input { line: 0, msg } => write!(f, "input: {msg}"),
+            CliError::Input { line, msg } => write!(f, "input line {line}: {msg}"),
+            CliError::NoResult(msg) => write!(f, "{msg}"),
+            CliError::Snapshot(err) => write!(f, "snapshot: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<std::io::Error> for CliError {
+    fn from(err: std::io::Error) -> Self {
+        CliError::Io(err)
+    }
+}
@@ -0,0 +1,53 @@
+use std::process::ExitCode;
+
+use nsc_arbiter_cli::{calibrate, parse_args, replay, CliError, Command, USAGE};
+
+fn main() -> ExitCode {
+    let result = parse_args(std::env::args().skip(1)).and_then(|cmd| match cmd {
+        Command::Help => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        Command::Replay(args) => replay::run(&args).map(|s| {
+            eprintln!(
+                "nsc_arbiter: {} events, {} ticks, {} actions ({} escalations)",
+                s.events, s.ticks, s.actions, s.escalations
+            );
+        }),
+        Command::Calibrate(args) => calibrate::run(&args).and_then(|report| {
+            eprintln!("nsc_arbiter: {} candidates, frontier:", report.evaluated);
+            eprintln!("  tau_e  tau_s  tau_gate  rep  stall  precision  recall  rate");
+            for p in &report.frontier {
+                eprintln!(
+                    "  {:<5}  {:<5}  {:<8}  {:<3}  {:<5}  {:<9.3}  {:<6.3}  {:.3}",
+                    p.tau_e, p.tau_s, p.tau_gate, p.tau_rep, p.tau_stall, p.precision, p.recall, p.escalation_rate
+                );
+            }
+            match report.chosen {
+                Some(p) => {
+                    eprintln!(
+                        "nsc_arbiter: chose tau_e={} tau_s={} tau_gate={} tau_rep={} tau_stall={}",
+                        p.tau_e, p.tau_s, p.tau_gate, p.tau_rep, p.tau_stall
+                    );
+                    Ok(())
+                }
+                None => Err(CliError::NoResult("no candidate satisfies --objective".to_string())),
+            }
+        }),
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(msg)) => {
+            if !msg.is_empty() {
+                eprintln!("nsc_arbiter: {msg}");
+            }
+            eprint!("{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(err) => {
+            eprintln!("nsc_arbiter: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! JSON shapes read and written by the CLI.
+
+use std::collections::BTreeMap;
+
+use nsc_arbiter_core::{DecisionTrace, Escalation, FreezeFlags, Hold};
+use nsc_arbiter_supervisor::{ActionEvent, SignalEvent};
+use serde::{Deserialize, Serialize};
+
+/// One input line: a `SignalEvent`.
+///
+/// ```json
+/// {"tick": 3, "intent_id": "q1", "source_id": "llm", "origin": "decoder",
+///  "text": "...", "scalars": {"entropy": 2.4, "cosine": 0.8}, "rule_hits": 0}
+/// ```
+///
+/// Only `intent_id` is required. Fields other than the ones below are kept in `extra`
+/// (that is where `--tick-field` is looked up).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct EventRecord {
+    pub intent_id: String,
+    #[serde(default)]
+    pub source_id: String,
+    #[serde(default)]
+    pub origin: String,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub scalars: BTreeMap<String, f32>,
+    #[serde(default)]
+    pub rule_hits: u32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl EventRecord {
+    pub fn to_signal(&self) -> SignalEvent<'_> {
+        let mut se = SignalEvent::new(self.intent_id.as_str(), self.source_id.as_str(), self.origin.as_str())
+            .with_rule_hits(self.rule_hits);
+        if let Some(text) = &self.text {
+            se = se.with_text(text.as_str());
+        }
+        for (k, v) in &self.scalars {
+            se = se.with_scalar(k.as_str(), *v);
+        }
+        se
+    }
+}
+
+/// Telemetry of one action (`ActionEvent::uncertainty`).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UncertaintyRecord {
+    pub avg_entropy: f32,
+    pub cosine_sim: f32,
+    pub rule_hits: u32,
+    pub gate_shift: f32,
+    pub total_weight: f32,
+    pub source_count: u32,
+    pub oddity: f32,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, f32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct FreezeFlagsRecord {
+    pub rep_3p: bool,
+    pub stall: bool,
+    pub ai_tell: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cross_tick_repeat: bool,
+}
+
+impl From<FreezeFlags> for FreezeFlagsRecord {
+    fn from(ff: FreezeFlags) -> Self {
+        Self { rep_3p: ff.rep_3p, stall: ff.stall, ai_tell: ff.ai_tell, cross_tick_repeat: ff.cross_tick_repeat }
+    }
+}
+
+/// One output line: an `ActionEvent` plus the logical time it was decided at.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ActionRecord {
+    pub tick: u64,
+    pub intent_id: String,
+    pub escalation: Escalation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub held: Option<Hold>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<UncertaintyRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freeze_flags: Option<FreezeFlagsRecord>,
+    /// Sources that raised a freeze flag, by `source_id`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub source_flags: BTreeMap<String, FreezeFlagsRecord>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DecisionTrace>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cohort: Option<String>,
+}
+
+impl ActionRecord {
+    pub fn new(tick: u64, a: ActionEvent) -> Self {
+        Self {
+            tick,
+            intent_id: a.intent_id,
+            escalation: a.escalation,
+            held: a.held,
+            uncertainty: a.uncertainty.map(|u| UncertaintyRecord {
+                avg_entropy: u.avg_entropy,
+                cosine_sim: u.cosine_sim,
+                rule_hits: u.rule_hits,
+                gate_shift: u.gate_shift,
+                total_weight: u.total_weight,
+                source_count: u.source_count,
+                oddity: u.oddity,
+                metrics: u.metrics.into_iter().map(|m| (m.name, m.value)).collect(),
+            }),
+            freeze_flags: a.freeze_flags.map(FreezeFlagsRecord::from),
+            source_flags: a.source_flags.into_iter().map(|(s, ff)| (s, ff.into())).collect(),
+            trace: a.trace,
+            cohort: a.cohort,
+        }
+    }
+}
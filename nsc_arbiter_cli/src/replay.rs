@@ -0,0 +1,153 @@
+//! `nsc_arbiter replay`: JSONL events in, JSONL actions out.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use nsc_arbiter_core::{ArbiterCfg, Escalation};
+use nsc_arbiter_supervisor::supervisor::SupervisorSnapshot;
+use nsc_arbiter_supervisor::{ArbiterSupervisor, BasicEvidenceBuilder, SignalEvent, SupervisorSnapshotV2};
+
+use crate::args::{Grouping, ReplayArgs};
+use crate::records::{ActionRecord, EventRecord};
+use crate::ticks::read_ticks;
+use crate::CliError;
+
+/// Counters for one run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunStats {
+    pub events: usize,
+    pub ticks: usize,
+    pub actions: usize,
+    /// Actions other than `Escalation::None`.
+    pub escalations: usize,
+}
+
+/// Run `replay` with file / stdio handling, per `args`.
+pub fn run(args: &ReplayArgs) -> Result<RunStats, CliError> {
+    let cfg = args.cfg.as_deref().map(read_to_string).transpose()?;
+    let snapshot = args.snapshot.as_deref().map(read_to_string).transpose()?;
+    let sup = build_supervisor(cfg.as_deref(), snapshot.as_deref(), args.shards, args.trace)?;
+
+    let input = open_input(args.input.as_deref())?;
+    let output = open_output(args.output.as_deref())?;
+    let stats = run_replay(&sup, &args.grouping, input, output)?;
+
+    if let Some(path) = &args.save_snapshot {
+        let mut w = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut w, &sup.snapshot_full())
+            .map_err(|err| CliError::Json { what: "save-snapshot".to_string(), line: 0, err })?;
+        w.write_all(b"\n")?;
+        w.flush()?;
+    }
+    Ok(stats)
+}
+
+/// Supervisor from optional cfg JSON (`ArbiterCfg`) and snapshot JSON.
+///
+/// A `SupervisorSnapshotV2` (has `schema_version`) restores configuration and state,
+/// with `cfg_json` replacing the base cfg if given. A state-only `SupervisorSnapshot`
+/// is migrated with `cfg_json` (or the default cfg). `shards` reshards a restored
+/// supervisor if it differs.
+pub fn build_supervisor(
+    cfg_json: Option<&str>,
+    snapshot_json: Option<&str>,
+    shards: Option<usize>,
+    trace: bool,
+) -> Result<ArbiterSupervisor, CliError> {
+    let cfg: Option<ArbiterCfg> = cfg_json
+        .map(|s| serde_json::from_str(s).map_err(|err| CliError::Json { what: "cfg".to_string(), line: 0, err }))
+        .transpose()?;
+
+    let mut sup = match snapshot_json {
+        None => ArbiterSupervisor::new(shards.unwrap_or(1), cfg.unwrap_or_default()),
+        Some(s) => {
+            let json_err = |err| CliError::Json { what: "snapshot".to_string(), line: 0, err };
+            let value: serde_json::Value = serde_json::from_str(s).map_err(json_err)?;
+            let full = if value.get("schema_version").is_some() {
+                serde_json::from_value::<SupervisorSnapshotV2>(value).map_err(json_err)?
+            } else {
+                let state: SupervisorSnapshot = serde_json::from_value(value).map_err(json_err)?;
+                SupervisorSnapshotV2::from_v1(state, cfg.clone().unwrap_or_default())
+            };
+            let mut sup = ArbiterSupervisor::restore_full(full).map_err(CliError::Snapshot)?;
+            if let Some(cfg) = cfg {
+                *sup.cfg_mut() = cfg;
+            }
+            if let Some(n) = shards {
+                if n != sup.shard_count() {
+                    sup.reshard(n);
+                }
+            }
+            sup
+        }
+    };
+    sup.set_trace(trace);
+    Ok(sup)
+}
+
+/// Stream events from `input`, ingest them tick by tick, write one JSON line per action.
+pub fn run_replay<R: BufRead, W: Write>(
+    sup: &ArbiterSupervisor,
+    grouping: &Grouping,
+    input: R,
+    mut output: W,
+) -> Result<RunStats, CliError> {
+    let builder = BasicEvidenceBuilder::default();
+    let mut stats = RunStats::default();
+    let events = read_ticks(input, grouping, |tick, key| {
+        ingest_tick(sup, &builder, tick, key, &mut output, &mut stats)
+    })?;
+    stats.events = events;
+    output.flush()?;
+    Ok(stats)
+}
+
+/// Ingest one tick: at the numeric `key` if there is one, else at the next clock value.
+fn ingest_tick<W: Write>(
+    sup: &ArbiterSupervisor,
+    builder: &BasicEvidenceBuilder,
+    tick: &[EventRecord],
+    key: Option<&serde_json::Value>,
+    output: &mut W,
+    stats: &mut RunStats,
+) -> Result<(), CliError> {
+    let events: Vec<SignalEvent<'_>> = tick.iter().map(EventRecord::to_signal).collect();
+    let actions = match key.and_then(|k| k.as_u64()) {
+        Some(now) => sup.ingest_at(builder, &events, now),
+        None => sup.ingest(builder, &events),
+    };
+    stats.ticks += 1;
+
+    let now = sup.clock();
+    for a in actions {
+        stats.actions += 1;
+        if a.escalation != Escalation::None {
+            stats.escalations += 1;
+        }
+        serde_json::to_writer(&mut *output, &ActionRecord::new(now, a))
+            .map_err(|err| CliError::Json { what: "output".to_string(), line: 0, err })?;
+        output.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+pub(crate) fn read_to_string(path: &Path) -> Result<String, CliError> {
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// `path`, or stdin for `None`.
+pub(crate) fn open_input(path: Option<&Path>) -> Result<Box<dyn BufRead>, CliError> {
+    Ok(match path {
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(io::stdin().lock()),
+    })
+}
+
+/// `path`, or stdout for `None`.
+pub(crate) fn open_output(path: Option<&Path>) -> Result<Box<dyn Write>, CliError> {
+    Ok(match path {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    })
+}
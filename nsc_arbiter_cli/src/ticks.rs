@@ -0,0 +1,59 @@
+//! JSONL input split into ticks (shared by every subcommand).
+
+use std::io::BufRead;
+
+use crate::args::Grouping;
+use crate::records::EventRecord;
+use crate::CliError;
+
+/// Read events from `input` and call `on_tick` once per tick, in input order, with the
+/// tick's events and its `--tick-field` value (`None` for fixed batches).
+/// Returns the number of events read.
+pub fn read_ticks<R, F>(input: R, grouping: &Grouping, mut on_tick: F) -> Result<usize, CliError>
+where
+    R: BufRead,
+    F: FnMut(&[EventRecord], Option<&serde_json::Value>) -> Result<(), CliError>,
+{
+    let mut events = 0;
+    let mut tick: Vec<EventRecord> = Vec::new();
+    let mut tick_key: Option<serde_json::Value> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let ev: EventRecord = serde_json::from_str(&line)
+            .map_err(|err| CliError::Json { what: "input".to_string(), line: line_no, err })?;
+        events += 1;
+
+        match grouping {
+            Grouping::Batch(n) => {
+                tick.push(ev);
+                if tick.len() >= *n {
+                    on_tick(&tick, None)?;
+                    tick.clear();
+                }
+            }
+            Grouping::Field(field) => {
+                let key = match ev.extra.get(field) {
+                    Some(v) => v.clone(),
+                    None => {
+                        return Err(CliError::Input { line: line_no, msg: format!("missing tick field `{field}`") })
+                    }
+                };
+                if tick_key.as_ref().is_some_and(|k| *k != key) {
+                    on_tick(&tick, tick_key.as_ref())?;
+                    tick.clear();
+                }
+                tick_key = Some(key);
+                tick.push(ev);
+            }
+        }
+    }
+    if !tick.is_empty() {
+        on_tick(&tick, tick_key.as_ref())?;
+    }
+    Ok(events)
+}
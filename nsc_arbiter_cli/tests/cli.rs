@@ -0,0 +1,115 @@
+use nsc_arbiter_cli::replay::build_supervisor;
+use nsc_arbiter_cli::{parse_args, run_replay, Command, Grouping, ReplayArgs, RunStats};
+
+fn args(s: &str) -> Vec<String> {
+    s.split_whitespace().map(str::to_string).collect()
+}
+
+fn lines(out: &[u8]) -> Vec<serde_json::Value> {
+    std::str::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+#[test]
+fn parses_replay_options() {
+    let cmd = parse_args(args("replay --input ev.jsonl --cfg cfg.json --tick-field ts --shards 4 --trace")).unwrap();
+    assert_eq!(
+        cmd,
+        Command::Replay(ReplayArgs {
+            input: Some("ev.jsonl".into()),
+            cfg: Some("cfg.json".into()),
+            grouping: Grouping::Field("ts".to_string()),
+            shards: Some(4),
+            trace: true,
+            ..ReplayArgs::default()
+        })
+    );
+
+    assert!(parse_args(args("replay --batch 0")).is_err());
+    assert!(parse_args(args("replay --batch 2 --tick-field ts")).is_err());
+    assert!(parse_args(args("replay --shards")).is_err());
+    assert!(parse_args(args("bogus")).is_err());
+}
+
+#[test]
+fn groups_ticks_by_field_and_writes_actions() {
+    let input = r#"
+{"ts": 10, "intent_id": "q1", "source_id": "llm", "scalars": {"entropy": 3.0, "cosine": 0.9}}
+{"ts": 10, "intent_id": "q2", "source_id": "llm", "scalars": {"entropy": 1.0, "cosine": 0.9}}
+{"ts": 12, "intent_id": "q1", "source_id": "llm", "scalars": {"entropy": 1.0, "cosine": 0.9}}
+"#;
+    let sup = build_supervisor(Some(r#"{"tau_e": 2.5}"#), None, None, true).unwrap();
+    let mut out = Vec::new();
+    let stats = run_replay(&sup, &Grouping::Field("ts".to_string()), input.as_bytes(), &mut out).unwrap();
+    assert_eq!(stats, RunStats { events: 3, ticks: 2, actions: 3, escalations: 1 });
+
+    let out = lines(&out);
+    assert_eq!(out[0]["tick"], 10);
+    assert_eq!(out[0]["intent_id"], "q1");
+    assert_eq!(out[0]["escalation"], "CritiquePass");
+    assert_eq!(out[0]["trace"]["escalation"], "CritiquePass");
+    assert_eq!(out[1]["escalation"], "None");
+    assert_eq!(out[2]["tick"], 12);
+
+    let missing = r#"{"intent_id": "q1"}"#;
+    assert!(run_replay(&sup, &Grouping::Field("ts".to_string()), missing.as_bytes(), Vec::new()).is_err());
+}
+
+#[test]
+fn starts_from_snapshot() {
+    let noisy = r#"{"intent_id": "q1", "source_id": "llm", "scalars": {"entropy": 3.0, "cosine": 0.9}}"#;
+    let sup = build_supervisor(Some(r#"{"cooldown_ticks": 5}"#), None, None, false).unwrap();
+    let mut out = Vec::new();
+    run_replay(&sup, &Grouping::Batch(1), noisy.as_bytes(), &mut out).unwrap();
+    assert_eq!(lines(&out)[0]["escalation"], "CritiquePass");
+
+    // The restored supervisor keeps the cfg and the cool-down it was in.
+    let snap = serde_json::to_string(&sup.snapshot_full()).unwrap();
+    let restored = build_supervisor(None, Some(&snap), Some(2), false).unwrap();
+    assert_eq!(restored.cfg().cooldown_ticks, 5);
+    assert_eq!(restored.shard_count(), 2);
+    let mut out = Vec::new();
+    run_replay(&restored, &Grouping::Batch(1), noisy.as_bytes(), &mut out).unwrap();
+    let out = lines(&out);
+    assert_eq!((out[0]["escalation"].as_str(), out[0]["held"].as_str()), (Some("None"), Some("Cooldown")));
+    assert_eq!(out[0]["tick"], 2);
+
+    // State-only snapshots are migrated with the given cfg.
+    let state = serde_json::to_string(&sup.snapshot()).unwrap();
+    let migrated = build_supervisor(Some(r#"{"tau_e": 9.0}"#), Some(&state), None, false).unwrap();
+    assert_eq!(migrated.cfg().tau_e, 9.0);
+    assert_eq!(migrated.snapshot(), sup.snapshot());
+}
+
+#[test]
+fn calibrate_reads_labelled_ticks() {
+    use nsc_arbiter_cli::labelled_sequences;
+    use nsc_arbiter_core::{calibrate, ArbiterCfg, CalibrationGrid, FreezeCfg, Objective};
+
+    let input = r#"
+{"ts": 1, "intent_id": "q1", "label": false, "scalars": {"entropy": 2.0, "cosine": 0.95}}
+{"ts": 1, "intent_id": "q2", "label": true, "scalars": {"entropy": 2.6, "cosine": 0.95}}
+{"ts": 2, "intent_id": "q1", "label": true, "scalars": {"entropy": 2.6, "cosine": 0.95}}
+{"ts": 2, "intent_id": "q1", "label": false, "scalars": {"entropy": 2.6, "cosine": 0.95}}
+"#;
+    let freeze = FreezeCfg::default();
+    let data = labelled_sequences(input.as_bytes(), &Grouping::Field("ts".to_string()), "label", &freeze).unwrap();
+    let shape: Vec<Vec<bool>> = data.iter().map(|s| s.ticks.iter().map(|t| t.should_escalate).collect()).collect();
+    assert_eq!(shape, vec![vec![false, true], vec![true]]);
+
+    let grid = CalibrationGrid { tau_e: vec![1.8, 2.4, 3.0], ..CalibrationGrid::default() };
+    let report = calibrate(&ArbiterCfg::default(), &data, &grid, Objective::F1);
+    assert_eq!(report.cfg.unwrap().tau_e, 2.4);
+
+    let unlabelled = r#"{"intent_id": "q1"}"#;
+    assert!(labelled_sequences(unlabelled.as_bytes(), &Grouping::Batch(1), "label", &freeze).is_err());
+
+    let cmd = parse_args(args("calibrate --tau-e 2:2.4:0.2 --tau-rep 1,2 --objective recall@0.1")).unwrap();
+    let Command::Calibrate(c) = cmd else { panic!("calibrate") };
+    assert_eq!((c.grid.tau_e, c.grid.tau_rep), (vec![2.0, 2.2, 2.4], vec![1, 2]));
+    assert_eq!(c.objective, Objective::RecallAtRate(0.1));
+    assert!(parse_args(args("calibrate --objective precision")).is_err());
+}
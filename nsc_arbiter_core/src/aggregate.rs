@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------
+// Aggregation strategies: how per-source evidence collapses into one
+// value per metric.
+// ---------------------------------------------------------------------
+
+/// One weighted observation of a metric.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    pub value: f32,
+    pub weight: f32,
+}
+
+/// Reduce weighted samples of one metric to a single value.
+///
+/// Callers only pass samples with positive weight, in evidence order, and never
+/// an empty slice. Implementations must be deterministic for a given input order.
+pub trait Aggregator {
+    fn aggregate(&self, samples: &[Sample]) -> f32;
+}
+
+/// Built-in aggregation strategies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Aggregation {
+    /// `sum(value * weight) / sum(weight)`.
+    #[default]
+    WeightedMean,
+    /// Smallest value at which the cumulative weight reaches half the total.
+    WeightedMedian,
+    /// Weighted mean after dropping `trim` (0..=0.5) of the total weight from each tail.
+    TrimmedMean { trim: f32 },
+    /// Largest value (worst case for higher-is-worse metrics).
+    Max,
+    /// Smallest value (worst case for lower-is-worse metrics such as cosine similarity).
+    Min,
+    /// Plain sum of values; weights only decide which samples count.
+    Sum,
+    /// 1.0 if any value is non-zero, else 0.0.
+    Any,
+}
+
+impl Aggregator for Aggregation {
+    fn aggregate(&self, samples: &[Sample]) -> f32 {
+        match *self {
+            Aggregation::WeightedMean => weighted_mean(samples),
+            Aggregation::WeightedMedian => weighted_median(samples),
+            Aggregation::TrimmedMean { trim } => trimmed_mean(samples, trim),
+            Aggregation::Max => samples.iter().map(|s| s.value).fold(f32::NEG_INFINITY, f32::max),
+            Aggregation::Min => samples.iter().map(|s| s.value).fold(f32::INFINITY, f32::min),
+            Aggregation::Sum => samples.iter().map(|s| s.value).sum(),
+            Aggregation::Any => {
+                if samples.iter().any(|s| s.value != 0.0) { 1.0 } else { 0.0 }
+            }
+        }
+    }
+}
+
+/// Per-metric aggregation choice. The default is the weighted mean, except for
+/// `rule_hits`, which takes the `Max`: a mean would let one guardrail hit round away
+/// as more sources report.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AggregationCfg {
+    pub avg_entropy: Aggregation,
+    pub cosine_sim: Aggregation,
+    pub gate_shift: Aggregation,
+    pub rule_hits: Aggregation,
+}
+
+impl Default for AggregationCfg {
+    fn default() -> Self {
+        Self {
+            avg_entropy: Aggregation::WeightedMean,
+            cosine_sim: Aggregation::WeightedMean,
+            gate_shift: Aggregation::WeightedMean,
+            rule_hits: Aggregation::Max,
+        }
+    }
+}
+
+fn weighted_mean(samples: &[Sample]) -> f32 {
+    let mut sum = 0.0_f32;
+    let mut sum_w = 0.0_f32;
+    for s in samples {
+        sum += s.value * s.weight;
+        sum_w += s.weight;
+    }
+    if sum_w > 0.0 { sum / sum_w } else { sum }
+}
+
+fn sorted(samples: &[Sample]) -> Vec<Sample> {
+    let mut v = samples.to_vec();
+    // Stable sort keeps ties in evidence order.
+    v.sort_by(|a, b| a.value.total_cmp(&b.value));
+    v
+}
+
+fn weighted_median(samples: &[Sample]) -> f32 {
+    let v = sorted(samples);
+    let half = v.iter().map(|s| s.weight).sum::<f32>() * 0.5;
+    let mut acc = 0.0_f32;
+    for s in &v {
+        acc += s.weight;
+        if acc >= half {
+            return s.value;
+        }
+    }
+    v.last().map_or(0.0, |s| s.value)
+}
+
+fn trimmed_mean(samples: &[Sample], trim: f32) -> f32 {
+    let trim = if trim.is_finite() { trim.clamp(0.0, 0.5) } else { 0.0 };
+    let v = sorted(samples);
+    let total: f32 = v.iter().map(|s| s.weight).sum();
+    let lo = total * trim;
+    let hi = total - lo;
+
+    let mut acc = 0.0_f32;
+    let mut sum = 0.0_f32;
+    let mut sum_w = 0.0_f32;
+    for s in &v {
+        let start = acc;
+        acc += s.weight;
+        // Portion of this sample's weight that falls inside [lo, hi].
+        let w = (acc.min(hi) - start.max(lo)).max(0.0);
+        sum += s.value * w;
+        sum_w += w;
+    }
+
+    if sum_w > 0.0 { sum / sum_w } else { weighted_median(samples) }
+}
@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cfg::ArbiterCfg;
+use crate::decide::{arbiter_persona_tick, arbiter_persona_tick_sources, Escalation};
+use crate::evidence::ArbiterEvidenceView;
+use crate::freeze::FreezeFlags;
+use crate::state::ArbiterState;
+
+// ---------------------------------------------------------------------
+// Offline threshold calibration against labelled history.
+// ---------------------------------------------------------------------
+
+/// One historical tick with the decision it should have produced.
+#[derive(Clone, Debug)]
+pub struct LabelledTick {
+    pub view: ArbiterEvidenceView,
+    pub freeze_flags: Option<FreezeFlags>,
+    /// Per-source flags; when non-empty they are counted per source instead of
+    /// `freeze_flags` (see `ArbiterState::bump_sources`).
+    pub source_flags: Vec<(String, FreezeFlags)>,
+    /// True if this tick should have escalated (any non-`None` outcome).
+    pub should_escalate: bool,
+}
+
+/// Ticks of one intent, oldest first. Each sequence starts from a fresh
+/// `ArbiterState` and is decided like the supervisor does (one logical time unit per
+/// tick, learned baselines), so hysteresis counts, decay and cool-down are calibrated too.
+#[derive(Clone, Debug, Default)]
+pub struct LabelledSequence {
+    pub ticks: Vec<LabelledTick>,
+}
+
+/// Candidate values per threshold. Every combination is evaluated, in a fixed order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationGrid {
+    pub tau_e: Vec<f32>,
+    pub tau_s: Vec<f32>,
+    pub tau_gate: Vec<f32>,
+    pub tau_rep: Vec<u32>,
+    pub tau_stall: Vec<u32>,
+}
+
+impl Default for CalibrationGrid {
+    /// A grid around the `ArbiterCfg::default()` thresholds.
+    fn default() -> Self {
+        Self {
+            tau_e: steps(1.0, 3.4, 0.2),
+            tau_s: steps(0.5, 0.95, 0.05),
+            tau_gate: steps(1.0, 3.0, 0.25),
+            tau_rep: vec![1, 2, 3],
+            tau_stall: vec![1, 2, 3],
+        }
+    }
+}
+
+impl CalibrationGrid {
+    /// Number of candidate configurations.
+    pub fn len(&self) -> usize {
+        self.tau_e.len() * self.tau_s.len() * self.tau_gate.len() * self.tau_rep.len() * self.tau_stall.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// `from, from + step, ...` up to `to` inclusive (rounded to avoid float drift).
+pub fn steps(from: f32, to: f32, step: f32) -> Vec<f32> {
+    if step.is_nan() || step <= 0.0 || from > to {
+        return vec![from];
+    }
+    let n = ((to - from) / step + 1e-4).floor() as usize;
+    (0..=n)
+        .map(|i| ((from + step * i as f32) * 1e4).round() / 1e4)
+        .collect()
+}
+
+/// How the reported configuration is picked from the evaluated points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Highest F1.
+    #[default]
+    F1,
+    /// Highest precision with recall at least this.
+    PrecisionAtRecall(f32),
+    /// Highest recall with escalation rate at most this.
+    RecallAtRate(f32),
+}
+
+/// Outcome of one candidate configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationPoint {
+    pub tau_e: f32,
+    pub tau_s: f32,
+    pub tau_gate: f32,
+    pub tau_rep: u32,
+    pub tau_stall: u32,
+    pub true_pos: u32,
+    pub false_pos: u32,
+    pub false_neg: u32,
+    pub true_neg: u32,
+    /// `tp / (tp + fp)`; 1.0 when nothing escalated.
+    pub precision: f32,
+    /// `tp / (tp + fn)`; 1.0 when nothing should have escalated.
+    pub recall: f32,
+    /// Share of ticks that escalated.
+    pub escalation_rate: f32,
+}
+
+impl CalibrationPoint {
+    pub fn f1(&self) -> f32 {
+        let s = self.precision + self.recall;
+        if s > 0.0 { 2.0 * self.precision * self.recall / s } else { 0.0 }
+    }
+
+    /// `base` with this point's thresholds.
+    pub fn apply(&self, base: &ArbiterCfg) -> ArbiterCfg {
+        ArbiterCfg {
+            tau_e: self.tau_e,
+            tau_s: self.tau_s,
+            tau_gate: self.tau_gate,
+            tau_rep: self.tau_rep,
+            tau_stall: self.tau_stall,
+            ..base.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    /// Points not dominated in (precision, recall, lower escalation rate), by recall
+    /// then precision, both descending.
+    pub frontier: Vec<CalibrationPoint>,
+    /// Best point under the objective; `None` if no point satisfies it.
+    pub chosen: Option<CalibrationPoint>,
+    /// `chosen` applied to the base cfg.
+    pub cfg: Option<ArbiterCfg>,
+    /// Candidates evaluated.
+    pub evaluated: usize,
+}
+
+/// Evaluate every grid point on `data` and pick one by `objective`.
+///
+/// Only the `tau_*` fields vary; everything else (ladder, cool-down, maturity gates,
+/// aggregation, metrics) comes from `base`. Deterministic: ties keep the earliest
+/// point in grid order.
+pub fn calibrate(
+    base: &ArbiterCfg,
+    data: &[LabelledSequence],
+    grid: &CalibrationGrid,
+    objective: Objective,
+) -> CalibrationReport {
+    let mut points = Vec::with_capacity(grid.len());
+    for &tau_e in &grid.tau_e {
+        for &tau_s in &grid.tau_s {
+            for &tau_gate in &grid.tau_gate {
+                for &tau_rep in &grid.tau_rep {
+                    for &tau_stall in &grid.tau_stall {
+                        let cfg = ArbiterCfg { tau_e, tau_s, tau_gate, tau_rep, tau_stall, ..base.clone() };
+                        points.push(evaluate(&cfg, data));
+                    }
+                }
+            }
+        }
+    }
+
+    let chosen = choose(&points, objective);
+    CalibrationReport {
+        frontier: frontier(&points),
+        cfg: chosen.map(|p| p.apply(base)),
+        chosen,
+        evaluated: points.len(),
+    }
+}
+
+fn evaluate(cfg: &ArbiterCfg, data: &[LabelledSequence]) -> CalibrationPoint {
+    let (mut tp, mut fp, mut fn_, mut tn) = (0u32, 0u32, 0u32, 0u32);
+    for seq in data {
+        let mut state = ArbiterState::default();
+        for (i, t) in seq.ticks.iter().enumerate() {
+            state.advance(i as u64 + 1, cfg);
+            let learned = state.learned_baselines(cfg);
+            let escalation = if t.source_flags.is_empty() {
+                arbiter_persona_tick(&t.view, t.freeze_flags, learned.as_ref(), cfg, &mut state)
+            } else {
+                arbiter_persona_tick_sources(&t.view, &t.source_flags, learned.as_ref(), cfg, &mut state)
+            };
+            state.learn(&t.view, cfg);
+            let escalated = escalation != Escalation::None;
+            match (escalated, t.should_escalate) {
+                (true, true) => tp += 1,
+                (true, false) => fp += 1,
+                (false, true) => fn_ += 1,
+                (false, false) => tn += 1,
+            }
+        }
+    }
+    let ratio = |num: u32, den: u32| if den == 0 { 1.0 } else { num as f32 / den as f32 };
+    let total = tp + fp + fn_ + tn;
+    CalibrationPoint {
+        tau_e: cfg.tau_e,
+        tau_s: cfg.tau_s,
+        tau_gate: cfg.tau_gate,
+        tau_rep: cfg.tau_rep,
+        tau_stall: cfg.tau_stall,
+        true_pos: tp,
+        false_pos: fp,
+        false_neg: fn_,
+        true_neg: tn,
+        precision: ratio(tp, tp + fp),
+        recall: ratio(tp, tp + fn_),
+        escalation_rate: if total == 0 { 0.0 } else { (tp + fp) as f32 / total as f32 },
+    }
+}
+
+fn choose(points: &[CalibrationPoint], objective: Objective) -> Option<CalibrationPoint> {
+    let score = |p: &CalibrationPoint| -> Option<f32> {
+        match objective {
+            Objective::F1 => Some(p.f1()),
+            Objective::PrecisionAtRecall(r) => (p.recall >= r).then_some(p.precision),
+            Objective::RecallAtRate(rate) => (p.escalation_rate <= rate).then_some(p.recall),
+        }
+    };
+    let mut best: Option<(f32, CalibrationPoint)> = None;
+    for p in points {
+        if let Some(s) = score(p) {
+            if best.is_none_or(|(b, _)| s > b) {
+                best = Some((s, *p));
+            }
+        }
+    }
+    best.map(|(_, p)| p)
+}
+
+fn frontier(points: &[CalibrationPoint]) -> Vec<CalibrationPoint> {
+    let dominates = |a: &CalibrationPoint, b: &CalibrationPoint| {
+        let ge = a.precision >= b.precision && a.recall >= b.recall && a.escalation_rate <= b.escalation_rate;
+        let gt = a.precision > b.precision || a.recall > b.recall || a.escalation_rate < b.escalation_rate;
+        ge && gt
+    };
+
+    // One representative (the earliest) per distinct outcome, then drop dominated ones.
+    let mut unique: Vec<CalibrationPoint> = Vec::new();
+    for p in points {
+        let same = |q: &CalibrationPoint| {
+            q.precision == p.precision && q.recall == p.recall && q.escalation_rate == p.escalation_rate
+        };
+        if !unique.iter().any(same) {
+            unique.push(*p);
+        }
+    }
+    let mut out: Vec<CalibrationPoint> =
+        unique.iter().filter(|p| !unique.iter().any(|q| dominates(q, p))).copied().collect();
+    out.sort_by(|a, b| b.recall.total_cmp(&a.recall).then(b.precision.total_cmp(&a.precision)));
+    out
+}
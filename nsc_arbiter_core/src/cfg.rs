@@ -1,4 +1,77 @@
-#[derive(Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::AggregationCfg;
+use crate::decide::Escalation;
+use crate::freeze::FreezeCfg;
+use crate::metrics::MetricRegistry;
+use crate::oddity::{BaselineLearning, OddityParams};
+
+/// One rung of the escalation ladder above `CritiquePass`.
+///
+/// The base `tau_*` thresholds of `ArbiterCfg` define the first rung (`CritiquePass`).
+/// Each `EscalationTier` is reached only after `min_ticks_below` consecutive ticks on
+/// the rung beneath it, and only while the uncertainty still crosses this rung's own
+/// thresholds. Rule hits count toward every rung.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EscalationTier {
+    pub escalation: Escalation,
+    pub tau_e: f32,
+    pub tau_s: f32,
+    pub tau_gate: f32,
+    /// Consecutive ticks required on the rung below before promotion.
+    pub min_ticks_below: u32,
+}
+
+impl EscalationTier {
+    /// A `SecondLLM` rung that reuses the default thresholds.
+    pub fn second_llm(min_ticks_below: u32) -> Self {
+        let d = ArbiterCfg::default();
+        Self {
+            escalation: Escalation::SecondLLM,
+            tau_e: d.tau_e,
+            tau_s: d.tau_s,
+            tau_gate: d.tau_gate,
+            min_ticks_below,
+        }
+    }
+}
+
+/// Which outcome wins when a tick warrants both a pause and a critique.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PausePriority {
+    /// Pause first: critiquing a looping generator only feeds the loop.
+    #[default]
+    PauseFirst,
+    /// Critique first; the pending pause fires once the signal is clean.
+    CritiqueFirst,
+}
+
+/// What `tau_ai_tell` does once reached.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiTellAction {
+    /// Pause with `PauseReason::AiTell`, after repetition and stall.
+    #[default]
+    Pause,
+    /// Count as an escalation signal, like a fired threshold. The counter is consumed
+    /// when the escalation goes out.
+    Escalate,
+}
+
+/// How hysteresis counters fade with logical time (see `ArbiterState::advance`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum HystDecay {
+    /// Counters only reset on a clean tick.
+    #[default]
+    None,
+    /// Counters halve every `half_life` time units.
+    Exponential { half_life: f32 },
+    /// Counters lose `per_unit` every time unit, floored at zero.
+    Linear { per_unit: f32 },
+}
+
+/// Serializable; fields missing from stored data take their default.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ArbiterCfg {
     pub tau_e: f32,
     pub tau_s: f32,
@@ -7,6 +80,38 @@ pub struct ArbiterCfg {
     pub tau_gate: f32,
     pub hyst_disable: bool,
     pub forced_rule_hits: Option<u32>,
+    /// Rungs above `CritiquePass`, lowest first. Empty keeps the arbiter single-tier.
+    pub ladder: Vec<EscalationTier>,
+    pub pause_priority: PausePriority,
+    pub hyst_decay: HystDecay,
+    /// After a ladder escalation, suppress further ones for this many decisions.
+    pub cooldown_ticks: u32,
+    /// After a ladder escalation, suppress further ones for this many time units.
+    pub cooldown_time: u64,
+    /// Maturity: hold escalations until a tick carries at least this much evidence weight.
+    pub min_evidence_weight: f32,
+    /// Maturity: hold escalations until a tick carries this many distinct sources.
+    pub min_sources: u32,
+    /// Maturity: hold escalations until this many consecutive ticks were over threshold.
+    pub min_dwell_ticks: u32,
+    /// How evidence is collapsed into `Uncertainty`, per metric.
+    pub aggregation: AggregationCfg,
+    /// Product-defined metrics; each one that fires counts like a built-in predicate
+    /// toward `CritiquePass`. Ladder rungs only look at the built-in metrics.
+    pub metrics: MetricRegistry,
+    /// Escalate to `CritiquePass` when `Uncertainty::oddity` exceeds this. Oddity lies in
+    /// [0, 1], so the default of 1.0 never fires. Ladder rungs ignore oddity.
+    pub tau_oddity: f32,
+    /// How per-metric z-scores against persona baselines become the oddity score.
+    pub oddity: OddityParams,
+    /// Learn persona baselines online from each tick's evidence (see
+    /// `ArbiterState::learned_baselines`). `None` disables learning.
+    pub baseline_learning: Option<BaselineLearning>,
+    /// Act once `hyst_ai_tell` (ticks flagged `ai_tell`) reaches this; 0 disables.
+    pub tau_ai_tell: u32,
+    pub ai_tell_action: AiTellAction,
+    /// How callers holding raw text derive `FreezeFlags` (see `freeze_flags_cfg`).
+    pub freeze: FreezeCfg,
 }
 
 impl Default for ArbiterCfg {
@@ -19,6 +124,22 @@ impl Default for ArbiterCfg {
             tau_gate: 2.0,
             hyst_disable: false,
             forced_rule_hits: None,
+            ladder: Vec::new(),
+            pause_priority: PausePriority::default(),
+            hyst_decay: HystDecay::default(),
+            cooldown_ticks: 0,
+            cooldown_time: 0,
+            min_evidence_weight: 0.0,
+            min_sources: 0,
+            min_dwell_ticks: 0,
+            aggregation: AggregationCfg::default(),
+            metrics: MetricRegistry::default(),
+            tau_oddity: 1.0,
+            oddity: OddityParams::default(),
+            baseline_learning: None,
+            tau_ai_tell: 0,
+            ai_tell_action: AiTellAction::default(),
+            freeze: FreezeCfg::default(),
         }
     }
 }
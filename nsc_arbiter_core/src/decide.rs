@@ -2,52 +2,356 @@
 
 use serde::Serialize;
 use serde::Deserialize;
-use crate::{evidence::Uncertainty, evidence::ArbiterEvidenceView, freeze::FreezeFlags, cfg::ArbiterCfg, state::ArbiterState};
+use crate::trace::{DecisionTrace, MetricTrace, Predicate, PredicateTrace};
+use crate::oddity::PersonaBaselines;
+use crate::{evidence::Uncertainty, evidence::ArbiterEvidenceView, freeze::FreezeFlags, cfg::AiTellAction, cfg::ArbiterCfg, cfg::EscalationTier, cfg::PausePriority, state::{ArbiterState, TickFlags}};
 
-/// Arbiter decision: stay, run a one-shot critic, or (later) ask a second LLM.
+/// Arbiter decision: stay, run a one-shot critic, ask a second LLM, or pause.
+///
+/// `SecondLLM` is only reachable through `ArbiterCfg::ladder`.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Escalation {
     None,
     CritiquePass,
     SecondLLM,
+    /// Stop feeding the generator: another pass would only extend a loop.
+    Pause { reason: PauseReason },
+}
+
+/// Why the arbiter asked for a pause.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PauseReason {
+    /// `hyst_rep` reached `tau_rep`.
+    Repetition,
+    /// `hyst_stall` reached `tau_stall`.
+    Stall,
+    /// Persistent assistant boilerplate (`FreezeFlags::ai_tell`).
+    AiTell,
 }
 
+/// Why a tick that warranted an escalation was answered with `None`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Hold {
+    /// Inside the post-escalation cool-down window.
+    Cooldown,
+    /// Tick evidence weight below `min_evidence_weight`.
+    MinEvidenceWeight,
+    /// Fewer distinct sources than `min_sources`.
+    MinSources,
+    /// Fewer consecutive over-threshold ticks than `min_dwell_ticks`.
+    MinDwell,
+}
+
+/// Decide one tick from already-counted hysteresis. The tick is taken to carry no
+/// freeze flags, so a tick without signal resets every counter; the tick functions
+/// pass their flags through and keep the counters of flags that fired.
 pub fn decide_escalation_cfg(
     u: Uncertainty,
     cfg: &ArbiterCfg,
     state: &mut ArbiterState,
 ) -> Escalation {
-    let hi_entropy = u.avg_entropy.is_finite() && u.avg_entropy > cfg.tau_e;
-    let low_sim    = u.cosine_sim.is_finite() && u.cosine_sim < cfg.tau_s;
+    decide(u, TickFlags::Intent(FreezeFlags::default()), cfg, state)
+}
+
+/// Same decision as `decide_escalation_cfg`, plus a `DecisionTrace` listing every
+/// predicate with its input, threshold, margin and whether it fired.
+pub fn decide_escalation_traced(
+    u: Uncertainty,
+    cfg: &ArbiterCfg,
+    state: &mut ArbiterState,
+) -> DecisionTrace {
+    decide_traced(u, TickFlags::Intent(FreezeFlags::default()), cfg, state)
+}
+
+fn decide(u: Uncertainty, ff: TickFlags<'_>, cfg: &ArbiterCfg, state: &mut ArbiterState) -> Escalation {
+    let ev = Eval::new(&u, cfg, state);
+    let (escalation, held) = ev.step(&u, ff, cfg, state);
+    state.last_hold = held;
+    escalation
+}
+
+fn decide_traced(u: Uncertainty, ff: TickFlags<'_>, cfg: &ArbiterCfg, state: &mut ArbiterState) -> DecisionTrace {
+    let ev = Eval::new(&u, cfg, state);
+    let predicates = ev.trace(&u, cfg);
+    let (escalation, held) = ev.step(&u, ff, cfg, state);
+    state.last_hold = held;
+    DecisionTrace { escalation, predicates, metrics: ev.metrics, held }
+}
+
+/// Predicates for one tick, evaluated once and shared by the plain and traced paths.
+struct Eval {
+    hi_entropy: bool,
+    low_sim: bool,
+    rules_bad: bool,
+    gate_bad: bool,
+    odd: bool,
+    rule_hits: u32,
+    rep_cnt: f32,
+    stall_cnt: f32,
+    ai_cnt: f32,
+    /// `hyst_ai_tell` reached a non-zero `tau_ai_tell`.
+    ai_tell: bool,
+    pause: Option<PauseReason>,
+    /// Registry metrics present in `u`, evaluated against their specs.
+    metrics: Vec<MetricTrace>,
+}
+
+impl Eval {
+    fn new(u: &Uncertainty, cfg: &ArbiterCfg, state: &ArbiterState) -> Self {
+        let rule_hits  = cfg.forced_rule_hits.unwrap_or(u.rule_hits);
+        let hi_entropy = u.avg_entropy.is_finite() && u.avg_entropy > cfg.tau_e;
+        let low_sim    = u.cosine_sim.is_finite() && u.cosine_sim < cfg.tau_s;
+        let rules_bad  = rule_hits > 0;
+        let gate_bad   = u.gate_shift.is_finite() && u.gate_shift > cfg.tau_gate;
+        let odd        = u.oddity.is_finite() && u.oddity > cfg.tau_oddity;
+
+        let rep_cnt   = if cfg.hyst_disable { 0.0 } else { state.hyst_rep };
+        let stall_cnt = if cfg.hyst_disable { 0.0 } else { state.hyst_stall };
+        let ai_cnt    = if cfg.hyst_disable { 0.0 } else { state.hyst_ai_tell };
+        let ai_tell   = cfg.tau_ai_tell > 0 && ai_cnt >= cfg.tau_ai_tell as f32;
+
+        let pause = if rep_cnt >= cfg.tau_rep.max(1) as f32 {
+            Some(PauseReason::Repetition)
+        } else if stall_cnt >= cfg.tau_stall.max(1) as f32 {
+            Some(PauseReason::Stall)
+        } else if ai_tell && cfg.ai_tell_action == AiTellAction::Pause {
+            Some(PauseReason::AiTell)
+        } else {
+            None
+        };
+
+        let metrics = u
+            .metrics
+            .iter()
+            .filter_map(|m| {
+                let spec = cfg.metrics.get(&m.name)?;
+                Some(MetricTrace {
+                    name: m.name.clone(),
+                    value: m.value,
+                    threshold: spec.threshold,
+                    margin: spec.margin(m.value),
+                    fired: spec.fires(m.value),
+                })
+            })
+            .collect();
+
+        Self { hi_entropy, low_sim, rules_bad, gate_bad, odd, rule_hits, rep_cnt, stall_cnt, ai_cnt, ai_tell, pause, metrics }
+    }
+
+    fn signal(&self) -> bool {
+        self.hi_entropy || self.low_sim || self.rules_bad || self.gate_bad || self.odd || self.metrics.iter().any(|m| m.fired)
+            || self.escalates_ai_tell()
+    }
+
+    /// `ai_tell` counts as a signal under `AiTellAction::Escalate`; under `Pause` it
+    /// shows up in `pause` instead.
+    fn escalates_ai_tell(&self) -> bool {
+        self.ai_tell && self.pause != Some(PauseReason::AiTell)
+    }
+
+    /// `ff` are the flags counted for this tick; a quiet tick keeps their counters.
+    fn step(
+        &self,
+        u: &Uncertainty,
+        ff: TickFlags<'_>,
+        cfg: &ArbiterCfg,
+        state: &mut ArbiterState,
+    ) -> (Escalation, Option<Hold>) {
+        let signal = self.signal();
+
+        if !signal && self.pause.is_none() {
+            state.reset_unflagged(ff);
+            state.step_down();
+            state.over_ticks = 0;
+            state.note_decision(false);
+            return (Escalation::None, None);
+        }
+
+        // Maturity gates apply to every non-`None` outcome, pauses included.
+        state.over_ticks = state.over_ticks.saturating_add(1);
+        if let Some(hold) = state.maturity_hold(u, cfg) {
+            state.note_decision(false);
+            return (Escalation::None, Some(hold));
+        }
+
+        match self.pause {
+            // A pause consumes the hysteresis that triggered it; the loop has to
+            // rebuild the counters before it can pause the intent again.
+            // Pauses are never held back by the cool-down.
+            Some(reason) if !signal || cfg.pause_priority == PausePriority::PauseFirst => {
+                state.reset();
+                state.note_decision(false);
+                (Escalation::Pause { reason }, None)
+            }
+            _ if state.in_cooldown(cfg) => {
+                state.note_decision(false);
+                (Escalation::None, Some(Hold::Cooldown))
+            }
+            _ => {
+                let esc = climb_ladder(u, cfg, state);
+                if self.escalates_ai_tell() {
+                    state.clear_ai_tell();
+                }
+                state.note_decision(true);
+                (esc, None)
+            }
+        }
+    }
+
+    fn trace(&self, u: &Uncertainty, cfg: &ArbiterCfg) -> Vec<PredicateTrace> {
+        let tau_rep = cfg.tau_rep.max(1) as f32;
+        let tau_stall = cfg.tau_stall.max(1) as f32;
+        let hits = self.rule_hits as f32;
+        let rep = self.rep_cnt;
+        let stall = self.stall_cnt;
+        let ai = self.ai_cnt;
+        let tau_ai = cfg.tau_ai_tell as f32;
+
+        Predicate::ALL
+            .iter()
+            .map(|&predicate| {
+                let (value, threshold, margin, fired) = match predicate {
+                    Predicate::HiEntropy => (u.avg_entropy, cfg.tau_e, u.avg_entropy - cfg.tau_e, self.hi_entropy),
+                    Predicate::LowSim => (u.cosine_sim, cfg.tau_s, cfg.tau_s - u.cosine_sim, self.low_sim),
+                    Predicate::RulesBad => (hits, 0.0, hits, self.rules_bad),
+                    Predicate::GateBad => (u.gate_shift, cfg.tau_gate, u.gate_shift - cfg.tau_gate, self.gate_bad),
+                    Predicate::RepCnt => (rep, tau_rep, rep - tau_rep, rep >= tau_rep),
+                    Predicate::StallCnt => (stall, tau_stall, stall - tau_stall, stall >= tau_stall),
+                    Predicate::Oddity => (u.oddity, cfg.tau_oddity, u.oddity - cfg.tau_oddity, self.odd),
+                    Predicate::AiTellCnt => (ai, tau_ai, ai - tau_ai, self.ai_tell),
+                };
+                PredicateTrace { predicate, value, threshold, margin, fired }
+            })
+            .collect()
+    }
+}
+
+fn tier_fires(tier: &EscalationTier, u: &Uncertainty, cfg: &ArbiterCfg) -> bool {
+    let hi_entropy = u.avg_entropy.is_finite() && u.avg_entropy > tier.tau_e;
+    let low_sim    = u.cosine_sim.is_finite() && u.cosine_sim < tier.tau_s;
     let rules_bad  = cfg.forced_rule_hits.unwrap_or(u.rule_hits) > 0;
-    let gate_bad   = u.gate_shift.is_finite() && u.gate_shift > cfg.tau_gate;
+    let gate_bad   = u.gate_shift.is_finite() && u.gate_shift > tier.tau_gate;
+    hi_entropy || low_sim || rules_bad || gate_bad
+}
+
+/// Pick the rung for a tick that already warrants at least `CritiquePass`.
+///
+/// A rung whose own thresholds no longer fire drops back towards `CritiquePass`; the
+/// next rung up is taken once the current one has been held for `min_ticks_below`
+/// consecutive ticks. At most one promotion happens per tick.
+fn climb_ladder(u: &Uncertainty, cfg: &ArbiterCfg, state: &mut ArbiterState) -> Escalation {
+    let top = cfg.ladder.len() as u32 + 1;
+    let mut tier = state.tier.clamp(1, top);
+
+    while tier > 1 && !tier_fires(&cfg.ladder[(tier - 2) as usize], u, cfg) {
+        tier -= 1;
+    }
 
-    if !hi_entropy && !low_sim && !rules_bad && !gate_bad {
-        state.reset();
-        return Escalation::None;
+    if tier == state.tier && tier < top {
+        let next = &cfg.ladder[(tier - 1) as usize];
+        if state.tier_ticks >= next.min_ticks_below && tier_fires(next, u, cfg) {
+            tier += 1;
+        }
     }
 
-    let rep_cnt   = if cfg.hyst_disable { 0 } else { state.hyst_rep };
-    let stall_cnt = if cfg.hyst_disable { 0 } else { state.hyst_stall };
+    if tier == state.tier {
+        state.tier_ticks = state.tier_ticks.saturating_add(1);
+    } else {
+        state.tier = tier;
+        state.tier_ticks = 1;
+    }
 
-    if hi_entropy || low_sim || rules_bad || gate_bad || rep_cnt >= cfg.tau_rep || stall_cnt >= cfg.tau_stall {
+    if tier == 1 {
         Escalation::CritiquePass
     } else {
-        Escalation::None
+        cfg.ladder[(tier - 2) as usize].escalation
     }
 }
 
+/// Bump hysteresis from `ff` and decide.
+///
+/// The tick functions are the one place freeze flags are counted: callers must not
+/// `ArbiterState::bump` the same flags themselves, or every flagged tick counts twice
+/// toward `tau_rep` / `tau_stall`.
+///
+/// Logical time does not move here: call `ArbiterState::advance` first when using
+/// `hyst_decay` or `cooldown_time`.
 pub fn arbiter_idle_tick(
     view: &ArbiterEvidenceView,
     ff: Option<FreezeFlags>,
     cfg: &ArbiterCfg,
     state: &mut ArbiterState,
+) -> Escalation {
+    arbiter_persona_tick(view, ff, None, cfg, state)
+}
+
+/// `arbiter_idle_tick` returning a full `DecisionTrace`.
+pub fn arbiter_idle_tick_traced(
+    view: &ArbiterEvidenceView,
+    ff: Option<FreezeFlags>,
+    cfg: &ArbiterCfg,
+    state: &mut ArbiterState,
+) -> DecisionTrace {
+    arbiter_persona_tick_traced(view, ff, None, cfg, state)
+}
+
+/// `arbiter_idle_tick` that also scores the view against the intent's persona
+/// `baselines` (see `ArbiterCfg::tau_oddity`).
+pub fn arbiter_persona_tick(
+    view: &ArbiterEvidenceView,
+    ff: Option<FreezeFlags>,
+    baselines: Option<&PersonaBaselines>,
+    cfg: &ArbiterCfg,
+    state: &mut ArbiterState,
 ) -> Escalation {
     if let Some(flags) = ff {
         state.bump(flags, cfg.hyst_disable);
     }
-    let u = view.to_uncertainty();
-    decide_escalation_cfg(u, cfg, state)
+    let u = view.to_uncertainty_persona(cfg, baselines);
+    decide(u, TickFlags::Intent(ff.unwrap_or_default()), cfg, state)
+}
+
+/// `arbiter_persona_tick` returning a full `DecisionTrace`.
+pub fn arbiter_persona_tick_traced(
+    view: &ArbiterEvidenceView,
+    ff: Option<FreezeFlags>,
+    baselines: Option<&PersonaBaselines>,
+    cfg: &ArbiterCfg,
+    state: &mut ArbiterState,
+) -> DecisionTrace {
+    if let Some(flags) = ff {
+        state.bump(flags, cfg.hyst_disable);
+    }
+    let u = view.to_uncertainty_persona(cfg, baselines);
+    decide_traced(u, TickFlags::Intent(ff.unwrap_or_default()), cfg, state)
+}
+
+/// `arbiter_persona_tick` with freeze flags per source: counted per source
+/// (see `ArbiterState::bump_sources`), so one source's flags cannot push another's
+/// toward a pause. An empty `ff` counts nothing, like `None`.
+pub fn arbiter_persona_tick_sources(
+    view: &ArbiterEvidenceView,
+    ff: &[(String, FreezeFlags)],
+    baselines: Option<&PersonaBaselines>,
+    cfg: &ArbiterCfg,
+    state: &mut ArbiterState,
+) -> Escalation {
+    state.bump_sources(ff, cfg.hyst_disable);
+    let u = view.to_uncertainty_persona(cfg, baselines);
+    decide(u, TickFlags::Sources(ff), cfg, state)
+}
+
+/// `arbiter_persona_tick_sources` returning a full `DecisionTrace`.
+pub fn arbiter_persona_tick_sources_traced(
+    view: &ArbiterEvidenceView,
+    ff: &[(String, FreezeFlags)],
+    baselines: Option<&PersonaBaselines>,
+    cfg: &ArbiterCfg,
+    state: &mut ArbiterState,
+) -> DecisionTrace {
+    state.bump_sources(ff, cfg.hyst_disable);
+    let u = view.to_uncertainty_persona(cfg, baselines);
+    decide_traced(u, TickFlags::Sources(ff), cfg, state)
 }
 
 /// Compatibility helper: decide escalation directly from an `ArbiterEvidenceView`.
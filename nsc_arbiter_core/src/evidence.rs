@@ -1,9 +1,24 @@
-#[derive(Clone, Copy, Debug, Default)]
+use crate::aggregate::{AggregationCfg, Aggregator, Sample};
+use crate::cfg::ArbiterCfg;
+use crate::metrics::{MetricRegistry, MetricValue};
+use crate::oddity::{compute_oddity, PersonaBaselines};
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Uncertainty {
     pub avg_entropy: f32,
     pub cosine_sim:  f32,
     pub rule_hits:   u32,
     pub gate_shift:  f32,
+    /// Sum of positive evidence weights behind this aggregate.
+    pub total_weight: f32,
+    /// Distinct `source_id`s with positive weight.
+    pub source_count: u32,
+    /// Deviation from the intent's persona baselines in [0, 1] (see `compute_oddity`).
+    /// 0.0 when no baselines are known.
+    pub oddity: f32,
+    /// Registry metrics, aggregated, in registry order.
+    /// Metrics that no weighted evidence reported are omitted.
+    pub metrics: Vec<MetricValue>,
 }
 
 #[derive(Clone, Debug)]
@@ -17,6 +32,8 @@ pub struct Evidence {
     pub cosine_sim: f32,
     pub rule_hits: u32,
     pub weight: f32,
+    /// Extra named scalars, matched by name against `ArbiterCfg::metrics`.
+    pub metrics: Vec<MetricValue>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -39,46 +56,109 @@ impl ArbiterEvidenceView {
 
     /// Aggregate all evidence into a single Uncertainty struct.
     /// Weighting is purely numeric and does not assume any extra semantics.
+    ///
+    /// Uses `AggregationCfg::default()` (weighted mean, `Max` for `rule_hits`); see
+    /// `to_uncertainty_with`.
     pub fn to_uncertainty(&self) -> Uncertainty {
+        self.to_uncertainty_with(&AggregationCfg::default())
+    }
+
+    /// Aggregate the way `cfg` asks: built-in metrics per `cfg.aggregation`, plus every
+    /// metric declared in `cfg.metrics`.
+    pub fn to_uncertainty_cfg(&self, cfg: &ArbiterCfg) -> Uncertainty {
+        let mut u = self.to_uncertainty_with(&cfg.aggregation);
+        u.metrics = self.metric_values(&cfg.metrics);
+        u
+    }
+
+    /// `to_uncertainty_cfg`, plus the oddity score against `baselines` when given.
+    pub fn to_uncertainty_persona(&self, cfg: &ArbiterCfg, baselines: Option<&PersonaBaselines>) -> Uncertainty {
+        let mut u = self.to_uncertainty_cfg(cfg);
+        if let Some(b) = baselines {
+            u.oddity = compute_oddity(self, b, &cfg.oddity);
+        }
+        u
+    }
+
+    /// Aggregate each registry metric over the weighted evidence that reports it.
+    pub fn metric_values(&self, registry: &MetricRegistry) -> Vec<MetricValue> {
+        let mut out = Vec::with_capacity(registry.len());
+        let mut buf: Vec<Sample> = Vec::new();
+        for spec in registry.iter() {
+            buf.clear();
+            for ev in &self.evidence {
+                let weight = ev.weight.max(0.0);
+                if weight == 0.0 {
+                    continue;
+                }
+                if let Some(m) = ev.metrics.iter().find(|m| m.name == spec.name) {
+                    buf.push(Sample { value: m.value, weight });
+                }
+            }
+            if !buf.is_empty() {
+                out.push(MetricValue::new(spec.name.clone(), spec.aggregation.aggregate(&buf)));
+            }
+        }
+        out
+    }
+
+    /// Aggregate using the per-metric strategies in `aggs` (usually `ArbiterCfg::aggregation`).
+    pub fn to_uncertainty_with(&self, aggs: &AggregationCfg) -> Uncertainty {
+        self.to_uncertainty_by(&aggs.avg_entropy, &aggs.cosine_sim, &aggs.gate_shift, &aggs.rule_hits)
+    }
+
+    /// Aggregate using caller-supplied `Aggregator`s, one per metric.
+    ///
+    /// Evidence with non-positive weight is ignored. `rule_hits` is rounded to the
+    /// nearest integer after aggregation.
+    pub fn to_uncertainty_by(
+        &self,
+        entropy: &dyn Aggregator,
+        cosine: &dyn Aggregator,
+        gate_shift: &dyn Aggregator,
+        rule_hits: &dyn Aggregator,
+    ) -> Uncertainty {
         if self.evidence.is_empty() {
             return Uncertainty {
                 avg_entropy: 0.0,
                 cosine_sim: 1.0,
                 rule_hits:  0,
                 gate_shift: 0.0,
+                total_weight: 0.0,
+                source_count: 0,
+                oddity: 0.0,
+                metrics: Vec::new(),
             };
         }
 
-        let mut sum_entropy    = 0.0_f32;
-        let mut sum_cos_sim    = 0.0_f32;
-        let mut sum_rule_hits  = 0.0_f32;
-        let mut sum_gate_shift = 0.0_f32;
-        let mut sum_w          = 0.0_f32;
+        let weighted: Vec<(&Evidence, f32)> = self
+            .evidence
+            .iter()
+            .map(|ev| (ev, ev.weight.max(0.0)))
+            .filter(|&(_, w)| w != 0.0)
+            .collect();
 
-        for ev in &self.evidence {
-            let w = ev.weight.max(0.0);
-            if w == 0.0 {
-                continue;
-            }
-            sum_entropy    += ev.avg_entropy * w;
-            sum_cos_sim    += ev.cosine_sim * w;
-            sum_rule_hits  += (ev.rule_hits as f32) * w;
-            sum_gate_shift += ev.gate_shift * w;
-            sum_w          += w;
-        }
+        let total_weight: f32 = weighted.iter().map(|&(_, w)| w).sum();
+        let mut sources: Vec<&str> = weighted.iter().map(|(ev, _)| ev.source_id.as_str()).collect();
+        sources.sort_unstable();
+        sources.dedup();
 
-        if sum_w > 0.0 {
-            sum_entropy    /= sum_w;
-            sum_cos_sim    /= sum_w;
-            sum_rule_hits  /= sum_w;
-            sum_gate_shift /= sum_w;
-        }
+        let mut buf: Vec<Sample> = Vec::with_capacity(weighted.len());
+        let mut reduce = |agg: &dyn Aggregator, metric: fn(&Evidence) -> f32| -> f32 {
+            buf.clear();
+            buf.extend(weighted.iter().map(|&(ev, weight)| Sample { value: metric(ev), weight }));
+            if buf.is_empty() { 0.0 } else { agg.aggregate(&buf) }
+        };
 
         Uncertainty {
-            avg_entropy: sum_entropy,
-            cosine_sim:  sum_cos_sim,
-            rule_hits:   sum_rule_hits.round() as u32,
-            gate_shift:  sum_gate_shift,
+            avg_entropy: reduce(entropy, |ev| ev.avg_entropy),
+            cosine_sim:  reduce(cosine, |ev| ev.cosine_sim),
+            gate_shift:  reduce(gate_shift, |ev| ev.gate_shift),
+            rule_hits:   reduce(rule_hits, |ev| ev.rule_hits as f32).round().max(0.0) as u32,
+            total_weight,
+            source_count: sources.len() as u32,
+            oddity: 0.0,
+            metrics: Vec::new(),
         }
     }
 }
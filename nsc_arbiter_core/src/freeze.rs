@@ -1,42 +1,349 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Phrases `freeze_flags` treats as assistant boilerplate.
+pub const DEFAULT_AI_PHRASES: [&str; 2] = ["as an ai", "as a language model"];
+
+/// Longest-repeated-substring detection only looks at this many trailing units, which
+/// bounds its quadratic cost per text.
+pub const LRS_MAX_UNITS: usize = 2048;
+
 /// Loom/freeze flags from deterministic heuristics.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct FreezeFlags {
-    pub rep_3p: bool,   // high 3-gram repetition rate
+    pub rep_3p: bool,   // repetition (n-gram, word n-gram or repeated substring)
     pub stall:  bool,   // very low diversity
     pub ai_tell: bool,  // "as an AI…" boilerplate
+    /// Near-duplicate of a recent tick's payload (see `TextMemory`); counts toward
+    /// repetition hysteresis like `rep_3p`.
+    pub cross_tick_repeat: bool,
 }
 
-/// Allocation-free freeze flags, character-based.
+impl FreezeFlags {
+    /// Whether any flag is set.
+    pub fn any(&self) -> bool {
+        self.rep_3p || self.stall || self.ai_tell || self.cross_tick_repeat
+    }
+
+    /// Flags set in either.
+    pub fn union(self, other: FreezeFlags) -> FreezeFlags {
+        FreezeFlags {
+            rep_3p: self.rep_3p || other.rep_3p,
+            stall: self.stall || other.stall,
+            ai_tell: self.ai_tell || other.ai_tell,
+            cross_tick_repeat: self.cross_tick_repeat || other.cross_tick_repeat,
+        }
+    }
+}
+
+/// Union of per-source flags; `None` when no source had text.
+pub fn union_flags(flags: &[(String, FreezeFlags)]) -> Option<FreezeFlags> {
+    flags.iter().map(|(_, ff)| *ff).reduce(FreezeFlags::union)
+}
+
+/// How `FreezeCfg::ai_phrases` are compared with text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaseFold {
+    /// Byte-exact substring match.
+    Exact,
+    /// ASCII letters compare case-insensitively.
+    #[default]
+    Ascii,
+    /// Full Unicode lowercasing on both sides.
+    Unicode,
+}
+
+/// Unit the n-gram and diversity heuristics count in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextUnit {
+    /// Raw bytes, n-grams on a stride of `ngram`; allocation-free fast path.
+    #[default]
+    Bytes,
+    /// Unicode scalar values, n-grams on a sliding window.
+    Chars,
+}
+
+/// Word n-gram repetition rule.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WordNgram {
+    /// Words per n-gram (at least 1).
+    pub n: usize,
+    /// `rep_3p` when the share of n-grams already seen earlier in the text exceeds this.
+    pub threshold: f32,
+}
+
+/// Freeze flag detection settings (see `freeze_flags_cfg`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FreezeCfg {
+    /// Substrings that set `ai_tell`. Empty phrases are ignored.
+    pub ai_phrases: Vec<String>,
+    pub case_fold: CaseFold,
+    pub unit: TextUnit,
+    /// n-gram length for the adjacent repetition ratio (at least 1).
+    pub ngram: usize,
+    /// `rep_3p` when the adjacent repetition ratio exceeds this.
+    pub rep_threshold: f32,
+    /// `stall` when unique units / total units falls below this.
+    pub stall_diversity: f32,
+    /// Word n-gram repetition; `None` disables. Words are whitespace-separated,
+    /// trimmed of punctuation and folded per `case_fold`.
+    pub word_ngram: Option<WordNgram>,
+    /// `rep_3p` when the longest repeated substring covers at least this share of
+    /// the text (in `unit`s); `None` disables. Only the last `LRS_MAX_UNITS` units
+    /// are searched.
+    pub lrs_threshold: Option<f32>,
+    /// Signatures of recent payloads remembered per (intent, source) for `cross_tick_repeat`;
+    /// `0` disables the memory.
+    pub recent_texts: usize,
+    /// Payloads whose `simhash` differs from a remembered one in at most this many
+    /// bits are near-duplicates. Unrelated texts differ in about 32.
+    pub near_duplicate_bits: u32,
+}
+
+impl Default for FreezeCfg {
+    fn default() -> Self {
+        Self {
+            ai_phrases: DEFAULT_AI_PHRASES.iter().map(|p| p.to_string()).collect(),
+            case_fold: CaseFold::default(),
+            unit: TextUnit::default(),
+            ngram: 3,
+            rep_threshold: 0.30,
+            stall_diversity: 0.12,
+            word_ngram: None,
+            lrs_threshold: None,
+            recent_texts: 0,
+            near_duplicate_bits: 10,
+        }
+    }
+}
+
+/// Raw heuristic scores behind the flags (see `freeze_scores`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FreezeScores {
+    /// Share of n-gram windows immediately followed by the same n-gram.
+    pub rep_ratio: f32,
+    /// Unique units / total units; `0.0` for empty text.
+    pub diversity: f32,
+    /// Present when `word_ngram` is set and the text has at least one word n-gram.
+    pub word_rep_ratio: Option<f32>,
+    /// Present when `lrs_threshold` is set; measured over the last `LRS_MAX_UNITS` units.
+    pub lrs_ratio: Option<f32>,
+}
+
+impl FreezeCfg {
+    /// Whether `text` contains any of `ai_phrases` under `case_fold`.
+    pub fn ai_tell(&self, text: &str) -> bool {
+        let text = self.fold(text);
+        self.ai_phrases.iter().filter(|p| !p.is_empty()).any(|p| text.contains(&self.fold(p)))
+    }
+
+    fn fold(&self, s: &str) -> String {
+        match self.case_fold {
+            CaseFold::Exact => s.to_string(),
+            CaseFold::Ascii => s.to_ascii_lowercase(),
+            CaseFold::Unicode => s.to_lowercase(),
+        }
+    }
+}
+
+/// Freeze flags with the default `FreezeCfg` (byte fast path).
 #[inline]
 pub fn freeze_flags(text: &str) -> FreezeFlags {
+    let mut ff = loop_flags(text);
+    let lo = text.trim().to_ascii_lowercase();
+    if DEFAULT_AI_PHRASES.iter().any(|p| lo.contains(p)) { ff.ai_tell = true; }
+    ff
+}
+
+/// Freeze flags detected per `cfg`.
+pub fn freeze_flags_cfg(text: &str, cfg: &FreezeCfg) -> FreezeFlags {
+    let s = text.trim();
+    let sc = freeze_scores(s, cfg);
+    FreezeFlags {
+        rep_3p: sc.rep_ratio > cfg.rep_threshold
+            || cfg.word_ngram.zip(sc.word_rep_ratio).is_some_and(|(w, r)| r > w.threshold)
+            || cfg.lrs_threshold.zip(sc.lrs_ratio).is_some_and(|(t, r)| r >= t),
+        stall: s.is_empty() || sc.diversity < cfg.stall_diversity,
+        ai_tell: cfg.ai_tell(s),
+        // Needs earlier payloads; set by whoever keeps a `TextMemory`.
+        cross_tick_repeat: false,
+    }
+}
+
+/// The scores `freeze_flags_cfg` compares against `cfg`'s thresholds.
+pub fn freeze_scores(text: &str, cfg: &FreezeCfg) -> FreezeScores {
+    let s = text.trim();
+    let n = cfg.ngram.max(1);
+    let (rep_ratio, diversity, lrs_ratio) = match cfg.unit {
+        TextUnit::Bytes => {
+            let b = s.as_bytes();
+            (strided_rep(b, n), byte_diversity(b), cfg.lrs_threshold.map(|_| lrs_ratio(b)))
+        }
+        TextUnit::Chars => {
+            let c: Vec<char> = s.chars().collect();
+            (sliding_rep(&c, n), char_diversity(&c), cfg.lrs_threshold.map(|_| lrs_ratio(&c)))
+        }
+    };
+    FreezeScores {
+        rep_ratio,
+        diversity,
+        word_rep_ratio: cfg.word_ngram.and_then(|w| word_rep(s, w.n.max(1), cfg)),
+        lrs_ratio,
+    }
+}
+
+/// `rep_3p` and `stall` with the default byte thresholds; allocation-free.
+fn loop_flags(text: &str) -> FreezeFlags {
     let mut ff = FreezeFlags::default();
     let s = text.trim();
     if s.is_empty() { ff.stall = true; return ff; }
+    let b = s.as_bytes();
+    if strided_rep(b, 3) > 0.30 { ff.rep_3p = true; }
+    if byte_diversity(b) < 0.12 { ff.stall = true; }
+    ff
+}
 
-    // 3-gram repetition (character-based)
+fn ratio(hits: usize, total: usize) -> f32 {
+    if total == 0 { 0.0 } else { hits as f32 / total as f32 }
+}
+
+/// Share of n-grams at offsets 0, n, 2n… equal to the n-gram right after them.
+fn strided_rep(b: &[u8], n: usize) -> f32 {
     let mut total = 0usize;
     let mut reps  = 0usize;
-    if s.len() >= 6 {
-        let b = s.as_bytes();
-        let mut i = 0usize;
-        while i + 6 <= b.len() {
-            total += 1;
-            if &b[i..i+3] == &b[i+3..i+6] { reps += 1; }
-            i += 3;
-        }
+    let mut i = 0usize;
+    while i + 2 * n <= b.len() {
+        total += 1;
+        if b[i..i+n] == b[i+n..i+2*n] { reps += 1; }
+        i += n;
     }
-    if total > 0 && (reps as f32) / (total as f32) > 0.30 { ff.rep_3p = true; }
+    ratio(reps, total)
+}
 
-    // Stall: unique-char diversity low
+/// Share of n-grams at every offset equal to the n-gram right after them.
+fn sliding_rep<T: PartialEq>(u: &[T], n: usize) -> f32 {
+    let windows = (u.len() + 1).saturating_sub(2 * n);
+    let reps = (0..windows).filter(|&i| u[i..i+n] == u[i+n..i+2*n]).count();
+    ratio(reps, windows)
+}
+
+fn byte_diversity(b: &[u8]) -> f32 {
     let mut uniq = [false; 256];
     let mut seen = 0usize;
-    for &ch in s.as_bytes() {
+    for &ch in b {
         let idx = ch as usize;
         if !uniq[idx] { uniq[idx] = true; seen += 1; }
     }
-    if (seen as f32) / (s.len() as f32) < 0.12 { ff.stall = true; }
+    ratio(seen, b.len())
+}
 
-    let lo = s.to_ascii_lowercase();
-    if lo.contains("as an ai") || lo.contains("as a language model") { ff.ai_tell = true; }
-    ff
-}
\ No newline at end of file
+fn char_diversity(c: &[char]) -> f32 {
+    let mut uniq = c.to_vec();
+    uniq.sort_unstable();
+    uniq.dedup();
+    ratio(uniq.len(), c.len())
+}
+
+/// Share of word n-grams that already occurred earlier in the text.
+fn word_rep(s: &str, n: usize, cfg: &FreezeCfg) -> Option<f32> {
+    let words: Vec<String> = s
+        .split_whitespace()
+        .map(|w| cfg.fold(w.trim_matches(|c: char| !c.is_alphanumeric())))
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.len() < n { return None; }
+    let mut seen = std::collections::HashSet::new();
+    let grams = words.windows(n);
+    let total = grams.len();
+    let reps = grams.filter(|g| !seen.insert(*g)).count();
+    Some(ratio(reps, total))
+}
+
+/// Longest substring occurring at least twice (occurrences may overlap), as a share
+/// of the last `LRS_MAX_UNITS` units; O(n²) time, O(n) space in those.
+fn lrs_ratio<T: PartialEq>(u: &[T]) -> f32 {
+    let u = &u[u.len().saturating_sub(LRS_MAX_UNITS)..];
+    let n = u.len();
+    // row[j]: longest common suffix of u[..i] and u[..j] for the current i < j.
+    let mut row = vec![0usize; n + 1];
+    let mut best = 0usize;
+    for i in 1..=n {
+        for j in (i + 1..=n).rev() {
+            row[j] = if u[i-1] == u[j-1] { row[j-1] + 1 } else { 0 };
+            best = best.max(row[j]);
+        }
+    }
+    ratio(best, n)
+}
+
+/// 64-bit SimHash of `text` over 4-char shingles, after `case_fold` and whitespace
+/// collapsing. Similar texts differ in few bits.
+pub fn simhash(text: &str, cfg: &FreezeCfg) -> u64 {
+    let folded = cfg.fold(text);
+    let chars: Vec<char> = folded.split_whitespace().flat_map(|w| w.chars().chain([' '])).collect();
+    let chars = &chars[..chars.len().saturating_sub(1)];
+    let mut weights = [0i32; 64];
+    let mut add = |shingle: &[char]| {
+        let h = shingle_hash(shingle);
+        for (bit, w) in weights.iter_mut().enumerate() {
+            *w += if h >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    };
+    if chars.len() < 4 {
+        add(chars);
+    } else {
+        chars.windows(4).for_each(&mut add);
+    }
+    weights.iter().enumerate().fold(0u64, |acc, (bit, &w)| if w > 0 { acc | 1 << bit } else { acc })
+}
+
+/// FNV-1a over the chars, then a splitmix64 finalizer so every bit is usable.
+fn shingle_hash(shingle: &[char]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &c in shingle {
+        for b in (c as u32).to_le_bytes() {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+/// Bounded memory of recent payload signatures, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextMemory {
+    pub signatures: VecDeque<u64>,
+}
+
+impl TextMemory {
+    /// Whether `sig` is within `near_duplicate_bits` of a remembered signature.
+    pub fn is_repeat(&self, sig: u64, cfg: &FreezeCfg) -> bool {
+        self.signatures.iter().any(|s| (s ^ sig).count_ones() <= cfg.near_duplicate_bits)
+    }
+
+    /// Remember `sig`, forgetting the oldest beyond `recent_texts`.
+    pub fn remember(&mut self, sig: u64, cfg: &FreezeCfg) {
+        self.signatures.push_back(sig);
+        while self.signatures.len() > cfg.recent_texts {
+            self.signatures.pop_front();
+        }
+    }
+
+    /// Check one tick's payloads against earlier ticks, then remember them.
+    /// Returns `cross_tick_repeat`; always `false` with the memory disabled.
+    pub fn observe<'a>(&mut self, texts: impl IntoIterator<Item = &'a str>, cfg: &FreezeCfg) -> bool {
+        if cfg.recent_texts == 0 {
+            return false;
+        }
+        let sigs: Vec<u64> = texts.into_iter().filter(|t| !t.trim().is_empty()).map(|t| simhash(t, cfg)).collect();
+        let repeat = sigs.iter().any(|&s| self.is_repeat(s, cfg));
+        for s in sigs {
+            self.remember(s, cfg);
+        }
+        repeat
+    }
+}
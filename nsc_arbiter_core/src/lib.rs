@@ -1,20 +1,42 @@
+pub mod calibrate;
 pub mod oddity;
 pub mod sources;
 
+pub mod aggregate;
 pub mod evidence;
+pub mod metrics;
 pub mod freeze;
 pub mod cfg;
 pub mod state;
 pub mod decide;
+pub mod trace;
 
-pub use oddity::{PersonaBaselines, OddityParams, compute_oddity};
-pub use sources::{SourceProfile, SourceProfiles, apply_source_profiles, default_source_profiles};
+pub use calibrate::{
+    CalibrationGrid, CalibrationPoint, CalibrationReport, LabelledSequence, LabelledTick, Objective, calibrate,
+};
+pub use oddity::{
+    BaselineEstimator, BaselineLearning, LearnedBaselines, PersonaBaselines, OddityParams, RunningStat, compute_oddity,
+};
+pub use sources::{
+    SourceProfile, SourceProfiles, SourceTrust, SourceTrusts, apply_source_profiles, apply_source_profiles_with_trust,
+    default_source_profiles,
+};
 
+pub use aggregate::{Aggregation, AggregationCfg, Aggregator, Sample};
 pub use evidence::{Uncertainty, Evidence, ArbiterEvidenceView};
-pub use freeze::{FreezeFlags, freeze_flags};
-pub use cfg::ArbiterCfg;
-pub use state::ArbiterState;
-pub use decide::{Escalation, decide_escalation_cfg, arbiter_idle_tick, decide_escalation_from_view};
+pub use metrics::{Direction, MetricRegistry, MetricSpec, MetricValue};
+pub use freeze::{
+    CaseFold, FreezeCfg, FreezeFlags, FreezeScores, TextMemory, TextUnit, WordNgram, DEFAULT_AI_PHRASES, LRS_MAX_UNITS,
+    freeze_flags, freeze_flags_cfg, freeze_scores, simhash, union_flags,
+};
+pub use cfg::{AiTellAction, ArbiterCfg, EscalationTier, HystDecay, PausePriority};
+pub use state::{ArbiterState, SourceHyst};
+pub use decide::{
+    Escalation, Hold, PauseReason, decide_escalation_cfg, decide_escalation_traced, arbiter_idle_tick,
+    arbiter_idle_tick_traced, arbiter_persona_tick, arbiter_persona_tick_traced, arbiter_persona_tick_sources,
+    arbiter_persona_tick_sources_traced, decide_escalation_from_view,
+};
+pub use trace::{DecisionTrace, MetricTrace, Predicate, PredicateTrace};
 
 // Optional: if you keep hyst_* compatibility shims
 pub use state::{hyst_reset, hyst_bump};
\ No newline at end of file
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::Aggregation;
+
+// ---------------------------------------------------------------------
+// Product-defined metrics beyond the four built-in Uncertainty fields.
+// ---------------------------------------------------------------------
+
+/// Which side of the threshold is bad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    /// Fires when the value is above the threshold.
+    HigherIsWorse,
+    /// Fires when the value is below the threshold.
+    LowerIsWorse,
+}
+
+/// Declaration of one named metric.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricSpec {
+    /// Scalar key carried on `Evidence::metrics`.
+    pub name: String,
+    pub aggregation: Aggregation,
+    pub direction: Direction,
+    pub threshold: f32,
+}
+
+impl MetricSpec {
+    pub fn new(name: impl Into<String>, aggregation: Aggregation, direction: Direction, threshold: f32) -> Self {
+        Self {
+            name: name.into(),
+            aggregation,
+            direction,
+            threshold,
+        }
+    }
+
+    /// Signed distance past the threshold in the bad direction.
+    #[inline]
+    pub fn margin(&self, value: f32) -> f32 {
+        match self.direction {
+            Direction::HigherIsWorse => value - self.threshold,
+            Direction::LowerIsWorse => self.threshold - value,
+        }
+    }
+
+    /// Whether `value` is on the bad side of the threshold. Non-finite values never fire.
+    #[inline]
+    pub fn fires(&self, value: f32) -> bool {
+        value.is_finite() && self.margin(value) > 0.0
+    }
+}
+
+/// Ordered set of metric declarations. Registration order is the order metrics
+/// appear in `Uncertainty::metrics`, traces and supervisor output.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricRegistry {
+    specs: Vec<MetricSpec>,
+}
+
+impl MetricRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a metric, replacing any earlier declaration with the same name in place.
+    pub fn register(&mut self, spec: MetricSpec) {
+        match self.specs.iter_mut().find(|s| s.name == spec.name) {
+            Some(existing) => *existing = spec,
+            None => self.specs.push(spec),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MetricSpec> {
+        self.specs.iter().find(|s| s.name == name)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, MetricSpec> {
+        self.specs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.specs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.specs.is_empty()
+    }
+}
+
+/// A named metric value, raw on `Evidence` or aggregated on `Uncertainty`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricValue {
+    pub name: String,
+    pub value: f32,
+}
+
+impl MetricValue {
+    pub fn new(name: impl Into<String>, value: f32) -> Self {
+        Self {
+            name: name.into(),
+            value,
+        }
+    }
+}
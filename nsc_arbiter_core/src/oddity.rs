@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::evidence::ArbiterEvidenceView;
 
 // ---------------------------------------------------------------------
@@ -6,10 +8,10 @@ use crate::evidence::ArbiterEvidenceView;
 
 /// Rolling baselines for a persona across a few core metrics.
 ///
-/// These can be populated from SQLite logs or kept in memory and
-/// periodically flushed. The intent is "per person, per time scale"
-/// not global population norms.
-#[derive(Clone, Debug, Default)]
+/// These can be populated from SQLite logs, kept in memory and
+/// periodically flushed, or learned online (see `LearnedBaselines`).
+/// The intent is "per person, per time scale" not global population norms.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct PersonaBaselines {
     pub gate_shift_mu: f32,
     pub gate_shift_sigma: f32,
@@ -24,7 +26,7 @@ pub struct PersonaBaselines {
 
 /// Parameters controlling how we convert per-metric z-scores into a
 /// single oddity score in [0,1].
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct OddityParams {
     /// z-threshold at which a metric is considered "surprising".
     pub z_thresh: f32,
@@ -47,10 +49,8 @@ impl Default for OddityParams {
 /// Compute a per-intent oddity score in [0,1] for this ArbiterEvidenceView,
 /// given per-person baselines and tunable parameters.
 ///
-/// This is deliberately kept separate from Uncertainty so you can
-/// decide later whether to:
-///   - feed it into `Uncertainty.gate_shift`, or
-///   - add a dedicated `oddity` field to Uncertainty.
+/// The decision path reports this as `Uncertainty.oddity` and compares it
+/// against `ArbiterCfg::tau_oddity`.
 pub fn compute_oddity(
     view: &ArbiterEvidenceView,
     baselines: &PersonaBaselines,
@@ -115,4 +115,103 @@ pub fn compute_oddity(
     let oddity_score = alpha * oddity_fraction + (1.0 - alpha) * magnitude_term;
 
     oddity_score.clamp(0.0, 1.0)
-}
\ No newline at end of file
+}
+// ---------------------------------------------------------------------
+// Online baseline learning
+// ---------------------------------------------------------------------
+
+/// Running estimator used to learn baselines.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BaselineEstimator {
+    /// Welford running mean and (population) variance over every sample seen.
+    Welford,
+    /// Exponentially weighted mean and variance; a sample's weight halves every
+    /// `half_life` later samples.
+    Ewma { half_life: f32 },
+}
+
+/// Online baseline learning settings (`ArbiterCfg::baseline_learning`).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BaselineLearning {
+    pub estimator: BaselineEstimator,
+    /// Samples (evidence records) to observe before learned baselines are used.
+    /// Until then oddity is reported as 0.
+    pub warmup: u64,
+}
+
+/// Running mean and variance of one metric.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunningStat {
+    pub n: u64,
+    pub mean: f32,
+    pub var: f32,
+}
+
+impl RunningStat {
+    pub fn update(&mut self, x: f32, estimator: BaselineEstimator) {
+        if !x.is_finite() {
+            return;
+        }
+        self.n = self.n.saturating_add(1);
+        if self.n == 1 {
+            self.mean = x;
+            self.var = 0.0;
+            return;
+        }
+        let d = x - self.mean;
+        match estimator {
+            BaselineEstimator::Welford => {
+                let n = self.n as f32;
+                self.mean += d / n;
+                self.var += (d * (x - self.mean) - self.var) / n;
+            }
+            BaselineEstimator::Ewma { half_life } => {
+                let alpha = if half_life > 0.0 { 1.0 - (-1.0 / half_life).exp2() } else { 1.0 };
+                let incr = alpha * d;
+                self.mean += incr;
+                self.var = (1.0 - alpha) * (self.var + d * incr);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn sigma(&self) -> f32 {
+        self.var.max(0.0).sqrt()
+    }
+}
+
+/// Per-intent baselines learned from the evidence the intent has seen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LearnedBaselines {
+    pub gate_shift: RunningStat,
+    pub entropy: RunningStat,
+    /// Cosine distance (1.0 - cosine_sim).
+    pub cos_dist: RunningStat,
+}
+
+impl LearnedBaselines {
+    /// Fold every positively weighted evidence record of `view` into the estimates.
+    pub fn observe(&mut self, view: &ArbiterEvidenceView, estimator: BaselineEstimator) {
+        for ev in view.evidence.iter().filter(|ev| ev.weight > 0.0) {
+            self.gate_shift.update(ev.gate_shift, estimator);
+            self.entropy.update(ev.avg_entropy, estimator);
+            self.cos_dist.update(1.0 - ev.cosine_sim, estimator);
+        }
+    }
+
+    /// Samples observed so far (the least-fed metric).
+    pub fn samples(&self) -> u64 {
+        self.gate_shift.n.min(self.entropy.n).min(self.cos_dist.n)
+    }
+
+    pub fn baselines(&self) -> PersonaBaselines {
+        PersonaBaselines {
+            gate_shift_mu: self.gate_shift.mean,
+            gate_shift_sigma: self.gate_shift.sigma(),
+            entropy_mu: self.entropy.mean,
+            entropy_sigma: self.entropy.sigma(),
+            cos_dist_mu: self.cos_dist.mean,
+            cos_dist_sigma: self.cos_dist.sigma(),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 use crate::evidence::ArbiterEvidenceView;
 
 // ---------------------------------------------------------------------
@@ -8,7 +9,7 @@ use crate::evidence::ArbiterEvidenceView;
 
 /// Per-source profile describing how much we trust this source by default
 /// and how far we let it deviate from that.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SourceProfile {
     /// Default weight when the Evidence doesn't specify one or specifies 0.
     pub base_weight: f32,
@@ -16,6 +17,13 @@ pub struct SourceProfile {
     pub min_weight: f32,
     /// Maximum allowed weight for this source.
     pub max_weight: f32,
+    /// Whether this source's text payloads are checked for freeze flags.
+    #[serde(default = "freeze_eligible_default")]
+    pub freeze_eligible: bool,
+}
+
+fn freeze_eligible_default() -> bool {
+    true
 }
 
 impl SourceProfile {
@@ -24,9 +32,35 @@ impl SourceProfile {
             base_weight,
             min_weight,
             max_weight,
+            freeze_eligible: true,
         }
     }
 
+    /// This profile with `freeze_eligible` set.
+    pub fn with_freeze_eligible(mut self, eligible: bool) -> Self {
+        self.freeze_eligible = eligible;
+        self
+    }
+
+    /// Profile assumed for sources missing from `SourceProfiles`: a soft hint
+    /// with low influence.
+    pub fn unknown() -> Self {
+        Self::new(0.2, 0.1, 0.4)
+    }
+
+    /// This profile with `base_weight` moved by a learned `reliability` in [-1, 1]:
+    /// 0 keeps it, 1 moves it to `max_weight`, -1 to `min_weight`.
+    pub fn with_reliability(&self, reliability: f32) -> Self {
+        let r = if reliability.is_finite() { reliability.clamp(-1.0, 1.0) } else { 0.0 };
+        let base = self.base_weight.clamp(self.min_weight, self.max_weight);
+        let base_weight = if r >= 0.0 {
+            base + r * (self.max_weight - base)
+        } else {
+            base + r * (base - self.min_weight)
+        };
+        Self { base_weight, ..self.clone() }
+    }
+
     /// Clamp a requested weight into this profile's [min, max] band,
     /// falling back to base_weight when requested is 0.
     pub fn clamp(&self, requested: f32) -> f32 {
@@ -37,6 +71,33 @@ impl SourceProfile {
 
 pub type SourceProfiles = HashMap<String, SourceProfile>;
 
+/// Learned reliability of one source, from escalation outcome feedback.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceTrust {
+    /// In [-1, 1]; 0 is neutral (the static profile).
+    pub reliability: f32,
+    /// Escalations this source contributed to that were reported useful.
+    pub useful: u64,
+    /// ... and reported not useful.
+    pub not_useful: u64,
+}
+
+impl SourceTrust {
+    /// Move `reliability` a fraction `rate` of the way towards +1 (useful) or -1.
+    pub fn record(&mut self, useful: bool, rate: f32) {
+        let rate = if rate.is_finite() { rate.clamp(0.0, 1.0) } else { 0.0 };
+        let target = if useful { 1.0 } else { -1.0 };
+        self.reliability = (self.reliability + rate * (target - self.reliability)).clamp(-1.0, 1.0);
+        if useful {
+            self.useful = self.useful.saturating_add(1);
+        } else {
+            self.not_useful = self.not_useful.saturating_add(1);
+        }
+    }
+}
+
+pub type SourceTrusts = HashMap<String, SourceTrust>;
+
 /// Apply source profiles to all Evidence items in an ArbiterEvidenceView.
 /// This does not change any other fields; it only adjusts `weight`.
 pub fn apply_source_profiles(view: &mut ArbiterEvidenceView, profiles: &SourceProfiles) {
@@ -45,13 +106,36 @@ pub fn apply_source_profiles(view: &mut ArbiterEvidenceView, profiles: &SourcePr
             ev.weight = p.clamp(ev.weight);
         } else {
             // Unknown source: treat as a soft hint with low influence.
-            // You can tune these defaults later.
-            let default_profile = SourceProfile::new(0.2, 0.1, 0.4);
-            ev.weight = default_profile.clamp(ev.weight);
+            ev.weight = SourceProfile::unknown().clamp(ev.weight);
         }
     }
 }
 
+/// `apply_source_profiles`, with each profile's `base_weight` moved by the source's
+/// learned reliability (see `SourceProfile::with_reliability`).
+///
+/// The clamped weight is scaled by how far `base_weight` moved, then clamped again,
+/// so the profile band stays the hard limit. Sources without trust are unchanged.
+pub fn apply_source_profiles_with_trust(
+    view: &mut ArbiterEvidenceView,
+    profiles: &SourceProfiles,
+    trust: &SourceTrusts,
+) {
+    for ev in &mut view.evidence {
+        let p = profiles.get(&ev.source_id).cloned().unwrap_or_else(SourceProfile::unknown);
+        let w = p.clamp(ev.weight);
+        ev.weight = match trust.get(&ev.source_id) {
+            Some(t) => {
+                let learned = p.with_reliability(t.reliability);
+                let base = p.base_weight.clamp(p.min_weight, p.max_weight);
+                let scaled = if base > 0.0 { w * learned.base_weight / base } else { learned.base_weight };
+                scaled.clamp(p.min_weight, p.max_weight)
+            }
+            None => w,
+        };
+    }
+}
+
 /// Build a small default SourceProfiles map.
 /// These are *relative* importance hints, not absolutes.
 /// You can override per-persona or from config.
@@ -1,26 +1,274 @@
+use std::collections::BTreeMap;
+
+use crate::cfg::{ArbiterCfg, HystDecay};
+use crate::decide::Hold;
+use crate::evidence::{ArbiterEvidenceView, Uncertainty};
 use crate::freeze::FreezeFlags;
+use crate::oddity::{LearnedBaselines, PersonaBaselines};
+
+/// Counters that decay below this are snapped to zero.
+const DECAY_FLOOR: f32 = 1e-3;
+
+/// Hysteresis counters of one source (see `ArbiterState::bump_sources`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SourceHyst {
+    pub rep: f32,
+    pub stall: f32,
+    pub ai_tell: f32,
+}
+
+impl SourceHyst {
+    fn is_zero(&self) -> bool {
+        self.rep == 0.0 && self.stall == 0.0 && self.ai_tell == 0.0
+    }
+}
 
-#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ArbiterState {
-    pub hyst_rep: u32,
-    pub hyst_stall: u32,
+    /// Repetition hysteresis. Whole ticks unless `ArbiterCfg::hyst_decay` is set.
+    pub hyst_rep: f32,
+    /// Stall hysteresis. Whole ticks unless `ArbiterCfg::hyst_decay` is set.
+    pub hyst_stall: f32,
+    /// Boilerplate (`ai_tell`) hysteresis. Whole ticks unless `ArbiterCfg::hyst_decay` is set.
+    #[serde(default)]
+    pub hyst_ai_tell: f32,
+    /// Current escalation rung: 0 = `None`, 1 = `CritiquePass`, `n >= 2` = `cfg.ladder[n - 2]`.
+    #[serde(default)]
+    pub tier: u32,
+    /// Consecutive ticks spent on the current rung.
+    #[serde(default)]
+    pub tier_ticks: u32,
+    /// Logical time of the last `advance` (`None` before the first one).
+    #[serde(default)]
+    pub last_ts: Option<u64>,
+    /// Logical time of the last ladder escalation, if `advance` was in use.
+    #[serde(default)]
+    pub last_escalation_ts: Option<u64>,
+    /// Decisions taken since the last ladder escalation (`None` if there never was one).
+    #[serde(default)]
+    pub ticks_since_escalation: Option<u32>,
+    /// Consecutive ticks that warranted an escalation (maturity dwell).
+    #[serde(default)]
+    pub over_ticks: u32,
+    /// Why the most recent decision was held back, if it was.
+    #[serde(default)]
+    pub last_hold: Option<Hold>,
+    /// Online persona baselines (only with `ArbiterCfg::baseline_learning`).
+    #[serde(default)]
+    pub learned: Option<LearnedBaselines>,
+    /// Per-source counters, only maintained by `bump_sources`. Sources whose counters
+    /// decay to zero are dropped.
+    #[serde(default)]
+    pub source_hyst: BTreeMap<String, SourceHyst>,
+}
+
+/// Flags a tick was counted with: for the whole intent (`bump`) or per source
+/// (`bump_sources`).
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum TickFlags<'a> {
+    Intent(FreezeFlags),
+    Sources(&'a [(String, FreezeFlags)]),
 }
 
 impl ArbiterState {
+    /// Count freeze flags toward hysteresis. The tick functions (`arbiter_idle_tick` and
+    /// friends) already do this. `decide_escalation_cfg` does not know the tick's flags,
+    /// so a quiet tick decided with it resets what was counted here; use the tick
+    /// functions for flags that must persist through quiet ticks.
     #[inline]
     pub fn bump(&mut self, ff: FreezeFlags, disable: bool) {
         if disable { return; }
-        if ff.rep_3p { self.hyst_rep += 1; }
-        if ff.stall  { self.hyst_stall += 1; }
+        if ff.rep_3p || ff.cross_tick_repeat { self.hyst_rep += 1.0; }
+        if ff.stall  { self.hyst_stall += 1.0; }
+        if ff.ai_tell { self.hyst_ai_tell += 1.0; }
+    }
+
+    /// Count per-source freeze flags toward hysteresis: each source keeps its own
+    /// counters, and the intent's counters rise only to the highest single source, so
+    /// flags from different sources never add up. The `_sources` tick functions
+    /// already do this.
+    pub fn bump_sources(&mut self, flags: &[(String, FreezeFlags)], disable: bool) {
+        if disable { return; }
+        for (source_id, ff) in flags {
+            if !ff.any() { continue; }
+            let h = self.source_hyst.entry(source_id.clone()).or_default();
+            if ff.rep_3p || ff.cross_tick_repeat { h.rep += 1.0; }
+            if ff.stall { h.stall += 1.0; }
+            if ff.ai_tell { h.ai_tell += 1.0; }
+            self.hyst_rep = self.hyst_rep.max(h.rep);
+            self.hyst_stall = self.hyst_stall.max(h.stall);
+            self.hyst_ai_tell = self.hyst_ai_tell.max(h.ai_tell);
+        }
     }
 
     #[inline]
     pub fn reset(&mut self) {
-        self.hyst_rep = 0;
-        self.hyst_stall = 0;
+        self.hyst_rep = 0.0;
+        self.hyst_stall = 0.0;
+        self.hyst_ai_tell = 0.0;
+        self.source_hyst.clear();
+    }
+
+    /// Reset the counters whose flag did not fire on this tick, so a loop keeps
+    /// building toward a pause through ticks without other signal. Each source is
+    /// reset by its own flags; with per-source flags the intent's counters are then
+    /// the highest remaining source's.
+    pub(crate) fn reset_unflagged(&mut self, flags: TickFlags<'_>) {
+        let own = |source_id: &str| match flags {
+            TickFlags::Intent(_) => FreezeFlags::default(),
+            TickFlags::Sources(ff) => {
+                ff.iter().find(|(s, _)| s == source_id).map_or(FreezeFlags::default(), |(_, f)| *f)
+            }
+        };
+        for (source_id, h) in self.source_hyst.iter_mut() {
+            let ff = own(source_id);
+            if !(ff.rep_3p || ff.cross_tick_repeat) { h.rep = 0.0; }
+            if !ff.stall { h.stall = 0.0; }
+            if !ff.ai_tell { h.ai_tell = 0.0; }
+        }
+        self.source_hyst.retain(|_, h| !h.is_zero());
+
+        match flags {
+            TickFlags::Intent(ff) => {
+                if !(ff.rep_3p || ff.cross_tick_repeat) { self.hyst_rep = 0.0; }
+                if !ff.stall { self.hyst_stall = 0.0; }
+                if !ff.ai_tell { self.hyst_ai_tell = 0.0; }
+            }
+            TickFlags::Sources(_) => {
+                let max = |f: fn(&SourceHyst) -> f32| self.source_hyst.values().map(f).fold(0.0, f32::max);
+                self.hyst_rep = max(|h| h.rep);
+                self.hyst_stall = max(|h| h.stall);
+                self.hyst_ai_tell = max(|h| h.ai_tell);
+            }
+        }
     }
+
+    /// Zero the `ai_tell` counters after acting on them.
+    pub(crate) fn clear_ai_tell(&mut self) {
+        self.hyst_ai_tell = 0.0;
+        for h in self.source_hyst.values_mut() {
+            h.ai_tell = 0.0;
+        }
+        self.source_hyst.retain(|_, h| !h.is_zero());
+    }
+
+    /// Drop one escalation rung after a clean tick.
+    #[inline]
+    pub fn step_down(&mut self) {
+        self.tier = self.tier.saturating_sub(1);
+        self.tier_ticks = 0;
+    }
+
+    /// Move the logical clock to `now`, decaying hysteresis by the elapsed time.
+    ///
+    /// Time is whatever unit the caller ticks in (ingest count, milliseconds, ...).
+    /// A clock that goes backwards is treated as no time passing. Call this before
+    /// deciding on every tick if you want decay or a time-based cool-down.
+    pub fn advance(&mut self, now: u64, cfg: &ArbiterCfg) {
+        let dt = match self.last_ts {
+            Some(last) => now.saturating_sub(last),
+            None => 0,
+        };
+        self.last_ts = Some(self.last_ts.map_or(now, |last| last.max(now)));
+
+        if dt == 0 {
+            return;
+        }
+        let dt = dt as f32;
+        match cfg.hyst_decay {
+            HystDecay::None => {}
+            HystDecay::Exponential { half_life } => {
+                let k = if half_life > 0.0 { (-dt / half_life).exp2() } else { 0.0 };
+                self.hyst_rep = decayed(self.hyst_rep * k);
+                self.hyst_stall = decayed(self.hyst_stall * k);
+                self.hyst_ai_tell = decayed(self.hyst_ai_tell * k);
+                for h in self.source_hyst.values_mut() {
+                    h.rep = decayed(h.rep * k);
+                    h.stall = decayed(h.stall * k);
+                    h.ai_tell = decayed(h.ai_tell * k);
+                }
+            }
+            HystDecay::Linear { per_unit } => {
+                let d = per_unit.max(0.0) * dt;
+                self.hyst_rep = decayed(self.hyst_rep - d);
+                self.hyst_stall = decayed(self.hyst_stall - d);
+                self.hyst_ai_tell = decayed(self.hyst_ai_tell - d);
+                for h in self.source_hyst.values_mut() {
+                    h.rep = decayed(h.rep - d);
+                    h.stall = decayed(h.stall - d);
+                    h.ai_tell = decayed(h.ai_tell - d);
+                }
+            }
+        }
+        self.source_hyst.retain(|_, h| !h.is_zero());
+    }
+
+    /// Advance the logical clock by one unit (starting at 0).
+    pub fn advance_tick(&mut self, cfg: &ArbiterCfg) {
+        let now = self.last_ts.map_or(0, |t| t.saturating_add(1));
+        self.advance(now, cfg);
+    }
+
+    /// Whether a ladder escalation right now would fall inside the cool-down window.
+    pub fn in_cooldown(&self, cfg: &ArbiterCfg) -> bool {
+        let by_ticks = self
+            .ticks_since_escalation
+            .is_some_and(|n| n < cfg.cooldown_ticks);
+        let by_time = match (self.last_ts, self.last_escalation_ts) {
+            (Some(now), Some(at)) => now.saturating_sub(at) < cfg.cooldown_time,
+            _ => false,
+        };
+        by_ticks || by_time
+    }
+
+    /// Which maturity gate, if any, holds back an escalation on a tick carrying `u`.
+    ///
+    /// Call after `over_ticks` has been updated for the current tick.
+    pub fn maturity_hold(&self, u: &Uncertainty, cfg: &ArbiterCfg) -> Option<Hold> {
+        if u.total_weight < cfg.min_evidence_weight {
+            Some(Hold::MinEvidenceWeight)
+        } else if u.source_count < cfg.min_sources {
+            Some(Hold::MinSources)
+        } else if self.over_ticks < cfg.min_dwell_ticks {
+            Some(Hold::MinDwell)
+        } else {
+            None
+        }
+    }
+
+    /// Fold `view` into the learned baselines, if `cfg.baseline_learning` is set.
+    ///
+    /// Call after deciding the tick, so a view is scored against what came before it.
+    pub fn learn(&mut self, view: &ArbiterEvidenceView, cfg: &ArbiterCfg) {
+        if let Some(l) = cfg.baseline_learning {
+            self.learned.get_or_insert_with(LearnedBaselines::default).observe(view, l.estimator);
+        }
+    }
+
+    /// Learned baselines once past the warm-up, else `None`.
+    pub fn learned_baselines(&self, cfg: &ArbiterCfg) -> Option<PersonaBaselines> {
+        let l = cfg.baseline_learning?;
+        let learned = self.learned.as_ref()?;
+        let n = learned.samples();
+        (n > 0 && n >= l.warmup).then(|| learned.baselines())
+    }
+
+    /// Record that a decision was taken, and whether it was a ladder escalation.
+    pub(crate) fn note_decision(&mut self, escalated: bool) {
+        if escalated {
+            self.ticks_since_escalation = Some(0);
+            self.last_escalation_ts = self.last_ts;
+        } else if let Some(n) = self.ticks_since_escalation.as_mut() {
+            *n = n.saturating_add(1);
+        }
+    }
+}
+
+#[inline]
+fn decayed(x: f32) -> f32 {
+    if x < DECAY_FLOOR { 0.0 } else { x }
 }
 
 // compat shims (optional)
 pub fn hyst_reset() { }
-pub fn hyst_bump(_ff: FreezeFlags) { }
\ No newline at end of file
+pub fn hyst_bump(_ff: FreezeFlags) { }
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use crate::decide::{Escalation, Hold};
+
+// ---------------------------------------------------------------------
+// Decision traces: which predicates fired, and by how much, on a tick.
+// ---------------------------------------------------------------------
+
+/// One input predicate of the escalation decision.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Predicate {
+    /// `avg_entropy > tau_e`
+    HiEntropy,
+    /// `cosine_sim < tau_s`
+    LowSim,
+    /// `rule_hits > 0` (after `forced_rule_hits`)
+    RulesBad,
+    /// `gate_shift > tau_gate`
+    GateBad,
+    /// `hyst_rep >= tau_rep`
+    RepCnt,
+    /// `hyst_stall >= tau_stall`
+    StallCnt,
+    /// `oddity > tau_oddity`
+    Oddity,
+    /// `hyst_ai_tell >= tau_ai_tell` (never fires while `tau_ai_tell == 0`)
+    AiTellCnt,
+}
+
+impl Predicate {
+    /// All predicates, in trace order.
+    pub const ALL: [Predicate; 8] = [
+        Predicate::HiEntropy,
+        Predicate::LowSim,
+        Predicate::RulesBad,
+        Predicate::GateBad,
+        Predicate::RepCnt,
+        Predicate::StallCnt,
+        Predicate::Oddity,
+        Predicate::AiTellCnt,
+    ];
+
+    /// Stable bit for this predicate in `DecisionTrace::fired_mask`.
+    #[inline]
+    pub fn bit(self) -> u32 {
+        1 << (self as u32)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Predicate::HiEntropy => "hi_entropy",
+            Predicate::LowSim => "low_sim",
+            Predicate::RulesBad => "rules_bad",
+            Predicate::GateBad => "gate_bad",
+            Predicate::RepCnt => "rep_cnt",
+            Predicate::StallCnt => "stall_cnt",
+            Predicate::Oddity => "oddity",
+            Predicate::AiTellCnt => "ai_tell_cnt",
+        }
+    }
+}
+
+/// Evaluation of a single predicate.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PredicateTrace {
+    pub predicate: Predicate,
+    /// Input value the predicate looked at.
+    pub value: f32,
+    /// Threshold it was compared against.
+    pub threshold: f32,
+    /// Signed distance past the threshold in the firing direction.
+    /// Positive (or zero for `>=` predicates) when the predicate fired.
+    pub margin: f32,
+    pub fired: bool,
+}
+
+/// Evaluation of one registry metric (see `ArbiterCfg::metrics`).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MetricTrace {
+    pub name: String,
+    pub value: f32,
+    pub threshold: f32,
+    /// Signed distance past the threshold in the metric's bad direction.
+    pub margin: f32,
+    pub fired: bool,
+}
+
+/// Structured explanation of one escalation decision.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DecisionTrace {
+    pub escalation: Escalation,
+    /// One entry per `Predicate::ALL`, in that order.
+    pub predicates: Vec<PredicateTrace>,
+    /// One entry per registry metric present on the tick, in registry order.
+    pub metrics: Vec<MetricTrace>,
+    /// Set when the predicates warranted an escalation that was held back.
+    pub held: Option<Hold>,
+}
+
+impl DecisionTrace {
+    /// Bitmask of fired predicates (see `Predicate::bit`).
+    pub fn fired_mask(&self) -> u32 {
+        self.predicates
+            .iter()
+            .filter(|p| p.fired)
+            .fold(0, |m, p| m | p.predicate.bit())
+    }
+
+    pub fn get(&self, predicate: Predicate) -> Option<&PredicateTrace> {
+        self.predicates.iter().find(|p| p.predicate == predicate)
+    }
+}
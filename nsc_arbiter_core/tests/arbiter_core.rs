@@ -2,7 +2,7 @@ use nsc_arbiter_core::*;
 
 #[test]
 fn by_entropy() {
-    let u = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0 };
+    let u = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
     let mut state = ArbiterState::default();
     let cfg = ArbiterCfg::default();
     assert_eq!(decide_escalation_cfg(u, &cfg, &mut state), Escalation::CritiquePass);
@@ -10,7 +10,7 @@ fn by_entropy() {
 
 #[test]
 fn clean() {
-    let u = Uncertainty { avg_entropy: 1.1, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0 };
+    let u = Uncertainty { avg_entropy: 1.1, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
     let mut state = ArbiterState::default();
     let cfg = ArbiterCfg::default();
     assert_eq!(decide_escalation_cfg(u, &cfg, &mut state), Escalation::None);
@@ -29,6 +29,7 @@ fn arbiter_view_weighted_aggregation() {
         cosine_sim: 1.0,
         rule_hits: 0,
         weight: 1.0,
+        metrics: Vec::new(),
     });
 
     view.push(Evidence {
@@ -40,11 +41,563 @@ fn arbiter_view_weighted_aggregation() {
         cosine_sim: 0.0,
         rule_hits: 2,
         weight: 1.0,
+        metrics: Vec::new(),
     });
 
     let u = view.to_uncertainty();
     assert!((u.avg_entropy - 2.0).abs() < 1e-6);
     assert!((u.cosine_sim - 0.5).abs() < 1e-6);
     assert!((u.gate_shift - 2.0).abs() < 1e-6);
-    assert_eq!(u.rule_hits, 1);
-}
\ No newline at end of file
+    assert_eq!(u.rule_hits, 2); // rule hits take the max
+}
+
+#[test]
+fn ladder_promotes_to_second_llm_and_steps_down() {
+    let noisy = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let clean = Uncertainty { avg_entropy: 1.1, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let cfg = ArbiterCfg {
+        ladder: vec![EscalationTier::second_llm(2)],
+        ..ArbiterCfg::default()
+    };
+    let mut state = ArbiterState::default();
+
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::CritiquePass);
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::CritiquePass);
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::SecondLLM);
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::SecondLLM);
+    assert_eq!(state.tier, 2);
+
+    assert_eq!(decide_escalation_cfg(clean.clone(), &cfg, &mut state), Escalation::None);
+    assert_eq!(state.tier, 1);
+    assert_eq!(decide_escalation_cfg(noisy, &cfg, &mut state), Escalation::CritiquePass);
+}
+
+#[test]
+fn ladder_rung_needs_its_own_threshold() {
+    let mild = Uncertainty { avg_entropy: 2.5, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let cfg = ArbiterCfg {
+        ladder: vec![EscalationTier { tau_e: 4.0, ..EscalationTier::second_llm(0) }],
+        ..ArbiterCfg::default()
+    };
+    let mut state = ArbiterState::default();
+
+    for _ in 0..4 {
+        assert_eq!(decide_escalation_cfg(mild.clone(), &cfg, &mut state), Escalation::CritiquePass);
+    }
+}
+
+#[test]
+fn repetition_pauses_instead_of_critiquing() {
+    let view = ArbiterEvidenceView::new("intent-1");
+    let rep = FreezeFlags { rep_3p: true, ..FreezeFlags::default() };
+    let cfg = ArbiterCfg::default();
+    let mut state = ArbiterState::default();
+
+    assert_eq!(
+        arbiter_idle_tick(&view, Some(rep), &cfg, &mut state),
+        Escalation::Pause { reason: PauseReason::Repetition }
+    );
+    assert_eq!(arbiter_idle_tick(&view, None, &cfg, &mut state), Escalation::None);
+}
+
+#[test]
+fn critique_first_defers_the_pause() {
+    let noisy = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let clean = Uncertainty { avg_entropy: 1.1, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let stall = FreezeFlags { stall: true, ..FreezeFlags::default() };
+    let mut state = ArbiterState::default();
+
+    let pause_first = ArbiterCfg::default();
+    state.bump(stall, false);
+    assert_eq!(
+        decide_escalation_cfg(noisy.clone(), &pause_first, &mut state),
+        Escalation::Pause { reason: PauseReason::Stall }
+    );
+
+    let critique_first = ArbiterCfg { pause_priority: PausePriority::CritiqueFirst, ..ArbiterCfg::default() };
+    state.bump(stall, false);
+    assert_eq!(decide_escalation_cfg(noisy, &critique_first, &mut state), Escalation::CritiquePass);
+    assert_eq!(
+        decide_escalation_cfg(clean, &critique_first, &mut state),
+        Escalation::Pause { reason: PauseReason::Stall }
+    );
+}
+
+#[test]
+fn trace_explains_the_decision() {
+    let u = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.5, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let cfg = ArbiterCfg::default();
+    let mut traced = ArbiterState::default();
+    let mut plain = ArbiterState::default();
+
+    let t = decide_escalation_traced(u.clone(), &cfg, &mut traced);
+    assert_eq!(t.escalation, decide_escalation_cfg(u, &cfg, &mut plain));
+    assert_eq!(t.escalation, Escalation::CritiquePass);
+    assert_eq!(t.predicates.len(), Predicate::ALL.len());
+    assert_eq!(t.fired_mask(), Predicate::HiEntropy.bit() | Predicate::LowSim.bit());
+
+    let ent = t.get(Predicate::HiEntropy).unwrap();
+    assert_eq!(ent.threshold, cfg.tau_e);
+    assert!((ent.margin - 0.8).abs() < 1e-6);
+
+    let sim = t.get(Predicate::LowSim).unwrap();
+    assert!((sim.margin - 0.26).abs() < 1e-6);
+    assert!(!t.get(Predicate::GateBad).unwrap().fired);
+}
+
+#[test]
+fn hysteresis_decays_with_logical_time() {
+    let rep = FreezeFlags { rep_3p: true, ..FreezeFlags::default() };
+    let cfg = ArbiterCfg {
+        tau_rep: 4,
+        hyst_decay: HystDecay::Exponential { half_life: 2.0 },
+        ..ArbiterCfg::default()
+    };
+    let mut state = ArbiterState::default();
+
+    state.advance(10, &cfg);
+    state.bump(rep, false);
+    state.bump(rep, false);
+    assert_eq!(state.hyst_rep, 2.0);
+
+    state.advance(12, &cfg);
+    assert!((state.hyst_rep - 1.0).abs() < 1e-6);
+
+    let linear = ArbiterCfg { hyst_decay: HystDecay::Linear { per_unit: 0.25 }, ..cfg };
+    state.advance(14, &linear);
+    assert!((state.hyst_rep - 0.5).abs() < 1e-6);
+    state.advance(100, &linear);
+    assert_eq!(state.hyst_rep, 0.0);
+}
+
+#[test]
+fn cooldown_holds_repeat_escalations() {
+    let noisy = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.9, rule_hits: 0, gate_shift: 0.0, ..Uncertainty::default() };
+    let cfg = ArbiterCfg { cooldown_ticks: 2, ..ArbiterCfg::default() };
+    let mut state = ArbiterState::default();
+
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::CritiquePass);
+    let held = decide_escalation_traced(noisy.clone(), &cfg, &mut state);
+    assert_eq!(held.escalation, Escalation::None);
+    assert_eq!(held.held, Some(Hold::Cooldown));
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::None);
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &cfg, &mut state), Escalation::CritiquePass);
+
+    let timed = ArbiterCfg { cooldown_time: 100, ..ArbiterCfg::default() };
+    let mut state = ArbiterState::default();
+    state.advance(1_000, &timed);
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &timed, &mut state), Escalation::CritiquePass);
+    state.advance(1_099, &timed);
+    assert_eq!(decide_escalation_cfg(noisy.clone(), &timed, &mut state), Escalation::None);
+    state.advance(1_100, &timed);
+    assert_eq!(decide_escalation_cfg(noisy, &timed, &mut state), Escalation::CritiquePass);
+}
+
+fn noisy_evidence(source_id: &str, weight: f32) -> Evidence {
+    Evidence {
+        source_id: source_id.to_string(),
+        intent_id: "intent-1".to_string(),
+        origin: "decoder".to_string(),
+        gate_shift: 0.0,
+        avg_entropy: 3.0,
+        cosine_sim: 0.9,
+        rule_hits: 0,
+        weight,
+        metrics: Vec::new(),
+    }
+}
+
+#[test]
+fn maturity_gates_hold_until_evidence_and_dwell() {
+    let cfg = ArbiterCfg {
+        min_evidence_weight: 1.5,
+        min_sources: 2,
+        min_dwell_ticks: 2,
+        ..ArbiterCfg::default()
+    };
+    let mut state = ArbiterState::default();
+
+    let mut thin = ArbiterEvidenceView::new("intent-1");
+    thin.push(noisy_evidence("llm", 1.0));
+    let t = arbiter_idle_tick_traced(&thin, None, &cfg, &mut state);
+    assert_eq!(t.escalation, Escalation::None);
+    assert_eq!(t.held, Some(Hold::MinEvidenceWeight));
+
+    let mut one_source = ArbiterEvidenceView::new("intent-1");
+    one_source.push(noisy_evidence("llm", 1.0));
+    one_source.push(noisy_evidence("llm", 1.0));
+    assert_eq!(arbiter_idle_tick(&one_source, None, &cfg, &mut state), Escalation::None);
+    assert_eq!(state.last_hold, Some(Hold::MinSources));
+
+    let mut mature = ArbiterEvidenceView::new("intent-1");
+    mature.push(noisy_evidence("llm", 1.0));
+    mature.push(noisy_evidence("stt", 1.0));
+    assert_eq!(arbiter_idle_tick(&mature, None, &cfg, &mut state), Escalation::CritiquePass);
+    assert_eq!(state.last_hold, None);
+
+    // A clean tick restarts the dwell count.
+    arbiter_idle_tick(&ArbiterEvidenceView::new("intent-1"), None, &cfg, &mut state);
+    assert_eq!(arbiter_idle_tick(&mature, None, &cfg, &mut state), Escalation::None);
+    assert_eq!(state.last_hold, Some(Hold::MinDwell));
+    assert_eq!(arbiter_idle_tick(&mature, None, &cfg, &mut state), Escalation::CritiquePass);
+}
+
+fn evidence_with(source_id: &str, avg_entropy: f32, rule_hits: u32) -> Evidence {
+    Evidence {
+        source_id: source_id.to_string(),
+        intent_id: "intent-1".to_string(),
+        origin: "test".to_string(),
+        gate_shift: 0.0,
+        avg_entropy,
+        cosine_sim: 1.0,
+        rule_hits,
+        weight: 1.0,
+        metrics: Vec::new(),
+    }
+}
+
+#[test]
+fn rule_hits_survive_sum_and_any_aggregation() {
+    let mut view = ArbiterEvidenceView::new("intent-1");
+    view.push(evidence_with("vendor", 1.0, 3));
+    for src in ["llm", "stt", "health", "meta_onnx"] {
+        view.push(evidence_with(src, 1.0, 0));
+    }
+
+    // A weighted mean rounds the lone trip away; the default `Max` keeps it.
+    let mean = AggregationCfg { rule_hits: Aggregation::WeightedMean, ..AggregationCfg::default() };
+    assert_eq!(view.to_uncertainty_with(&mean).rule_hits, 1);
+    view.push(evidence_with("audio_gen", 1.0, 0));
+    view.push(evidence_with("video_gen", 1.0, 0));
+    assert_eq!(view.to_uncertainty_with(&mean).rule_hits, 0);
+    assert_eq!(view.to_uncertainty().rule_hits, 3);
+
+    let sum = AggregationCfg { rule_hits: Aggregation::Sum, ..AggregationCfg::default() };
+    assert_eq!(view.to_uncertainty_with(&sum).rule_hits, 3);
+
+    let any = AggregationCfg { rule_hits: Aggregation::Any, ..AggregationCfg::default() };
+    let cfg = ArbiterCfg { aggregation: any, ..ArbiterCfg::default() };
+    assert_eq!(view.to_uncertainty_with(&any).rule_hits, 1);
+    let mut state = ArbiterState::default();
+    assert_eq!(arbiter_idle_tick(&view, None, &cfg, &mut state), Escalation::CritiquePass);
+}
+
+#[test]
+fn robust_aggregators_ignore_outliers() {
+    let s = |value: f32, weight: f32| Sample { value, weight };
+    let samples = [s(1.0, 1.0), s(1.2, 1.0), s(0.8, 1.0), s(9.0, 1.0)];
+
+    assert!((Aggregation::WeightedMean.aggregate(&samples) - 3.0).abs() < 1e-6);
+    assert_eq!(Aggregation::WeightedMedian.aggregate(&samples), 1.0);
+    assert!((Aggregation::TrimmedMean { trim: 0.25 }.aggregate(&samples) - 1.1).abs() < 1e-6);
+    assert_eq!(Aggregation::Max.aggregate(&samples), 9.0);
+    assert_eq!(Aggregation::Min.aggregate(&samples), 0.8);
+
+    // Weight moves the median.
+    let heavy = [s(1.0, 1.0), s(5.0, 3.0)];
+    assert_eq!(Aggregation::WeightedMedian.aggregate(&heavy), 5.0);
+}
+
+#[test]
+fn registry_metric_drives_escalation() {
+    let mut cfg = ArbiterCfg::default();
+    cfg.metrics.register(MetricSpec::new("toxicity", Aggregation::Max, Direction::HigherIsWorse, 0.8));
+    cfg.metrics.register(MetricSpec::new("coverage", Aggregation::WeightedMean, Direction::LowerIsWorse, 0.5));
+
+    let mut view = ArbiterEvidenceView::new("intent-1");
+    let mut llm = evidence_with("llm", 1.0, 0);
+    llm.metrics = vec![MetricValue::new("toxicity", 0.2), MetricValue::new("unregistered", 9.0)];
+    let mut moderation = evidence_with("moderation", 1.0, 0);
+    moderation.metrics = vec![MetricValue::new("toxicity", 0.9)];
+    view.push(llm);
+    view.push(moderation);
+
+    // Built-ins are clean; only the registered metric that evidence reported shows up.
+    let u = view.to_uncertainty_cfg(&cfg);
+    assert_eq!(u.metrics, vec![MetricValue::new("toxicity", 0.9)]);
+
+    let mut state = ArbiterState::default();
+    let t = arbiter_idle_tick_traced(&view, None, &cfg, &mut state);
+    assert_eq!(t.escalation, Escalation::CritiquePass);
+    assert_eq!(t.fired_mask(), 0);
+    assert_eq!(t.metrics.len(), 1);
+    assert!(t.metrics[0].fired);
+    assert!((t.metrics[0].margin - 0.1).abs() < 1e-6);
+
+    // Without the registry the same evidence is clean.
+    let mut state = ArbiterState::default();
+    assert_eq!(arbiter_idle_tick(&view, None, &ArbiterCfg::default(), &mut state), Escalation::None);
+}
+
+#[test]
+fn oddity_against_persona_baselines() {
+    let baselines = PersonaBaselines {
+        gate_shift_mu: 0.0,
+        gate_shift_sigma: 0.1,
+        entropy_mu: 1.0,
+        entropy_sigma: 0.1,
+        cos_dist_mu: 0.0,
+        cos_dist_sigma: 0.05,
+    };
+    let mut view = ArbiterEvidenceView::new("intent-1");
+    // Inside the global thresholds, far outside this persona's norm.
+    view.push(Evidence { cosine_sim: 0.8, ..evidence_with("llm", 2.0, 0) });
+
+    let cfg = ArbiterCfg { tau_oddity: 0.5, ..ArbiterCfg::default() };
+    let u = view.to_uncertainty_persona(&cfg, Some(&baselines));
+    assert!(u.oddity > 0.5, "oddity {}", u.oddity);
+    assert_eq!(view.to_uncertainty_persona(&cfg, None).oddity, 0.0);
+
+    let mut state = ArbiterState::default();
+    assert_eq!(arbiter_idle_tick(&view, None, &cfg, &mut state), Escalation::None);
+    let t = arbiter_persona_tick_traced(&view, None, Some(&baselines), &cfg, &mut state);
+    assert_eq!(t.escalation, Escalation::CritiquePass);
+    assert_eq!(t.fired_mask(), Predicate::Oddity.bit());
+
+    // Disabled by default.
+    let mut state = ArbiterState::default();
+    let default_cfg = ArbiterCfg::default();
+    assert_eq!(
+        arbiter_persona_tick(&view, None, Some(&baselines), &default_cfg, &mut state),
+        Escalation::None
+    );
+}
+
+#[test]
+fn baselines_learn_online_after_warmup() {
+    let view_with = |entropy: f32| {
+        let mut view = ArbiterEvidenceView::new("intent-1");
+        view.push(evidence_with("llm", entropy, 0));
+        view
+    };
+
+    let mut welford = RunningStat::default();
+    for x in [1.0, 2.0, 3.0, 4.0] {
+        welford.update(x, BaselineEstimator::Welford);
+    }
+    assert!((welford.mean - 2.5).abs() < 1e-6);
+    assert!((welford.var - 1.25).abs() < 1e-6);
+
+    // EWMA forgets: after a level shift the mean tracks the new level.
+    let mut ewma = RunningStat::default();
+    for _ in 0..10 {
+        ewma.update(1.0, BaselineEstimator::Ewma { half_life: 2.0 });
+    }
+    for _ in 0..20 {
+        ewma.update(3.0, BaselineEstimator::Ewma { half_life: 2.0 });
+    }
+    assert!((ewma.mean - 3.0).abs() < 0.01);
+
+    let cfg = ArbiterCfg {
+        baseline_learning: Some(BaselineLearning { estimator: BaselineEstimator::Welford, warmup: 3 }),
+        ..ArbiterCfg::default()
+    };
+    let mut state = ArbiterState::default();
+    for (i, x) in [1.0, 1.2, 0.8].into_iter().enumerate() {
+        assert!(state.learned_baselines(&cfg).is_none(), "warming up at {i}");
+        state.learn(&view_with(x), &cfg);
+    }
+    let b = state.learned_baselines(&cfg).expect("warmed up");
+    assert!((b.entropy_mu - 1.0).abs() < 1e-6);
+    assert!(b.entropy_sigma > 0.0);
+    assert!(view_with(2.0).to_uncertainty_persona(&cfg, Some(&b)).oddity > 0.5);
+
+    // Learning is off without `baseline_learning`.
+    let mut plain = ArbiterState::default();
+    plain.learn(&view_with(1.0), &ArbiterCfg::default());
+    assert!(plain.learned.is_none());
+}
+
+#[test]
+fn source_trust_moves_weight_inside_band() {
+    let p = SourceProfile::new(0.6, 0.2, 0.8);
+    assert_eq!(p.with_reliability(0.0).base_weight, 0.6);
+    assert!((p.with_reliability(1.0).base_weight - 0.8).abs() < 1e-6);
+    assert!((p.with_reliability(-0.5).base_weight - 0.4).abs() < 1e-6);
+
+    let mut t = SourceTrust::default();
+    t.record(true, 0.5);
+    t.record(true, 0.5);
+    assert!((t.reliability - 0.75).abs() < 1e-6);
+    t.record(false, 0.5);
+    assert!((t.reliability - -0.125).abs() < 1e-6);
+    assert_eq!((t.useful, t.not_useful), (2, 1));
+
+    let mut profiles = SourceProfiles::new();
+    profiles.insert("vendor".to_string(), p);
+    let mut trust = SourceTrusts::new();
+    trust.insert("vendor".to_string(), SourceTrust { reliability: -1.0, ..SourceTrust::default() });
+
+    let mut view = ArbiterEvidenceView::new("intent-1");
+    view.push(Evidence { weight: 0.6, ..evidence_with("vendor", 1.0, 0) });
+    view.push(Evidence { weight: 0.6, ..evidence_with("stt", 1.0, 0) });
+    apply_source_profiles_with_trust(&mut view, &profiles, &trust);
+    // Fully distrusted: pinned to the band floor, never below it.
+    assert!((view.evidence[0].weight - 0.2).abs() < 1e-6);
+    // Unknown, untrusted source: unknown profile only.
+    assert!((view.evidence[1].weight - 0.4).abs() < 1e-6);
+}
+
+#[test]
+fn calibration_finds_separating_threshold() {
+    // Entropy 2.0 should stay quiet, 2.6 should escalate; cosine is always healthy.
+    let tick = |entropy: f32, should_escalate: bool| {
+        let mut view = ArbiterEvidenceView::new("intent-1");
+        view.push(Evidence { cosine_sim: 0.95, ..evidence_with("llm", entropy, 0) });
+        LabelledTick { view, freeze_flags: None, source_flags: Vec::new(), should_escalate }
+    };
+    let data = vec![
+        LabelledSequence { ticks: vec![tick(2.0, false), tick(2.6, true), tick(2.0, false)] },
+        LabelledSequence { ticks: vec![tick(2.6, true), tick(2.6, true)] },
+    ];
+    let grid = CalibrationGrid { tau_e: vec![1.8, 2.2, 2.8], ..CalibrationGrid::default() };
+
+    let report = calibrate(&ArbiterCfg::default(), &data, &grid, Objective::F1);
+    assert_eq!(report.evaluated, grid.len());
+    let chosen = report.chosen.unwrap();
+    assert_eq!(chosen.tau_e, 2.2);
+    assert_eq!((chosen.precision, chosen.recall), (1.0, 1.0));
+    assert_eq!(chosen.escalation_rate, 0.6);
+    assert_eq!(report.cfg.unwrap().tau_e, 2.2);
+
+    // The frontier runs from the perfect point down to never escalating.
+    let ends: Vec<_> = report.frontier.iter().map(|p| (p.recall, p.escalation_rate)).collect();
+    assert_eq!(ends, vec![(1.0, 0.6), (0.0, 0.0)]);
+
+    // A rate cap below the positive share forces the quiet end of the grid.
+    let capped = calibrate(&ArbiterCfg::default(), &data, &grid, Objective::RecallAtRate(0.5)).chosen.unwrap();
+    assert_eq!((capped.tau_e, capped.recall), (2.8, 0.0));
+    assert_eq!(calibrate::steps(0.5, 0.7, 0.1), vec![0.5, 0.6, 0.7]);
+
+    // Ticks advance the clock like the supervisor does, so hysteresis decays.
+    let rep = FreezeFlags { rep_3p: true, ..FreezeFlags::default() };
+    let looping = |should_escalate| LabelledTick { freeze_flags: Some(rep), ..tick(1.0, should_escalate) };
+    let data = vec![LabelledSequence { ticks: vec![looping(false), looping(true)] }];
+    let grid = CalibrationGrid { tau_e: vec![2.2], tau_rep: vec![2], ..CalibrationGrid::default() };
+    let steady = calibrate(&ArbiterCfg::default(), &data, &grid, Objective::F1).chosen.unwrap();
+    assert_eq!((steady.true_pos, steady.false_pos), (1, 0));
+    let decaying = ArbiterCfg { hyst_decay: HystDecay::Linear { per_unit: 1.0 }, ..ArbiterCfg::default() };
+    let decayed = calibrate(&decaying, &data, &grid, Objective::F1).chosen.unwrap();
+    assert_eq!((decayed.true_pos, decayed.false_neg), (0, 1));
+}
+
+#[test]
+fn ai_tell_phrases_and_decision_rule() {
+    let text = "Als KI-Sprachmodell kann ich das nicht.";
+    assert!(!freeze_flags(text).ai_tell);
+    let mut freeze = FreezeCfg { ai_phrases: vec!["als ki-sprachmodell".to_string()], ..FreezeCfg::default() };
+    assert!(freeze_flags_cfg(text, &freeze).ai_tell);
+    freeze.case_fold = CaseFold::Exact;
+    assert!(!freeze_flags_cfg(text, &freeze).ai_tell);
+    freeze.ai_phrases = vec!["ÜBER MICH".to_string()];
+    freeze.case_fold = CaseFold::Unicode;
+    assert!(freeze_flags_cfg("über mich", &freeze).ai_tell);
+    assert_eq!(freeze_flags("As an AI, I cannot."), freeze_flags_cfg("As an AI, I cannot.", &FreezeCfg::default()));
+
+    let u = Uncertainty { avg_entropy: 3.0, cosine_sim: 0.9, ..Uncertainty::default() };
+    let ai = FreezeFlags { ai_tell: true, ..FreezeFlags::default() };
+    let tick = |cfg: &ArbiterCfg, state: &mut ArbiterState| {
+        state.bump(ai, cfg.hyst_disable);
+        decide_escalation_traced(u.clone(), cfg, state)
+    };
+
+    // Off by default: boilerplate is counted but never acted on.
+    let cfg = ArbiterCfg::default();
+    let mut state = ArbiterState::default();
+    for _ in 0..3 {
+        assert_eq!(tick(&cfg, &mut state).escalation, Escalation::CritiquePass);
+    }
+    assert_eq!(state.hyst_ai_tell, 3.0);
+
+    let cfg = ArbiterCfg { tau_ai_tell: 2, ..ArbiterCfg::default() };
+    let mut state = ArbiterState::default();
+    assert_eq!(tick(&cfg, &mut state).escalation, Escalation::CritiquePass);
+    let t = tick(&cfg, &mut state);
+    assert_eq!(t.escalation, Escalation::Pause { reason: PauseReason::AiTell });
+    assert!(t.get(Predicate::AiTellCnt).unwrap().fired);
+    assert_eq!(state.hyst_ai_tell, 0.0);
+
+    // Boilerplate alone keeps counting through quiet ticks; a clean tick resets it.
+    let quiet = ArbiterEvidenceView::new("intent-1");
+    let mut state = ArbiterState::default();
+    assert_eq!(arbiter_idle_tick(&quiet, Some(ai), &cfg, &mut state), Escalation::None);
+    assert_eq!(state.hyst_ai_tell, 1.0);
+    assert_eq!(
+        arbiter_idle_tick(&quiet, Some(ai), &cfg, &mut state),
+        Escalation::Pause { reason: PauseReason::AiTell }
+    );
+    arbiter_idle_tick(&quiet, Some(ai), &cfg, &mut state);
+    assert_eq!(arbiter_idle_tick(&quiet, None, &cfg, &mut state), Escalation::None);
+    assert_eq!(state.hyst_ai_tell, 0.0);
+}
+
+#[test]
+fn char_word_and_lrs_repetition() {
+    let bytes = FreezeCfg::default();
+    let chars = FreezeCfg { unit: TextUnit::Chars, ..FreezeCfg::default() };
+    for t in ["abcabcabcabc", "abababababababababab", "the quick brown fox", "", "As an AI, I cannot."] {
+        assert_eq!(freeze_flags_cfg(t, &bytes), freeze_flags(t), "{t:?}");
+    }
+
+    // CJK: long varied prose shares few distinct bytes, so byte mode reads it as a stall...
+    let prose: String = (0..300).map(|i| char::from_u32(0x4E00 + (i * 7919) % 2000).unwrap()).collect();
+    assert!(freeze_flags(&prose).stall);
+    assert_eq!(freeze_flags_cfg(&prose, &chars), FreezeFlags::default());
+    // ...and a two-char loop never matches on the three-byte stride.
+    let cjk = "你好".repeat(6);
+    assert!(!freeze_flags(&cjk).rep_3p);
+    assert!(freeze_flags_cfg(&cjk, &FreezeCfg { ngram: 2, ..chars.clone() }).rep_3p);
+
+    // Offset by one char: the stride never lines up with the repeated unit.
+    let off = "xabcdabcdabcdabcdabcd";
+    assert!(!freeze_flags(off).rep_3p);
+    let cfg = FreezeCfg { ngram: 4, ..chars.clone() };
+    assert!(freeze_scores(off, &cfg).rep_ratio > 0.5);
+    assert!(freeze_flags_cfg(off, &cfg).rep_3p);
+
+    // Word n-grams: the same sentence twice, with different case and punctuation.
+    let words = "The model cannot answer that. the model cannot answer that!";
+    assert!(!freeze_flags(words).rep_3p);
+    let cfg = FreezeCfg { word_ngram: Some(WordNgram { n: 3, threshold: 0.3 }), ..FreezeCfg::default() };
+    let sc = freeze_scores(words, &cfg);
+    assert!((sc.word_rep_ratio.unwrap() - 3.0 / 8.0).abs() < 1e-6);
+    assert!(freeze_flags_cfg(words, &cfg).rep_3p);
+    assert_eq!(freeze_scores("too short", &cfg).word_rep_ratio, None);
+
+    // Longest repeated substring: a long copied span, not adjacent.
+    let lrs = "first, we check the inputs carefully; then, we check the inputs carefully";
+    let cfg = FreezeCfg { lrs_threshold: Some(0.4), ..chars };
+    assert!(!freeze_flags(lrs).rep_3p);
+    let r = freeze_scores(lrs, &cfg).lrs_ratio.unwrap();
+    assert!(r > 0.4 && r < 0.6, "{r}");
+    assert!(freeze_flags_cfg(lrs, &cfg).rep_3p);
+    assert_eq!(freeze_scores(lrs, &FreezeCfg::default()).lrs_ratio, None);
+    // Only the last `LRS_MAX_UNITS` units are searched.
+    let long = format!("{lrs} {}", "abcdefghijklmnopqrstuvwxyz0123456789".repeat(LRS_MAX_UNITS / 36 + 1));
+    let tail: String = long.chars().rev().take(LRS_MAX_UNITS).collect::<Vec<_>>().into_iter().rev().collect();
+    assert_eq!(freeze_scores(&long, &cfg).lrs_ratio, freeze_scores(&tail, &cfg).lrs_ratio);
+}
+
+#[test]
+fn text_memory_flags_near_duplicates() {
+    let cfg = FreezeCfg { recent_texts: 2, ..FreezeCfg::default() };
+    let para = "The quarterly report shows revenue grew in every region, led by strong demand overseas.";
+    let unrelated = "Shipping delays eased after the port reopened and carriers added capacity this month.";
+    let reflowed = "  the QUARTERLY report shows revenue grew in every region,\nled by strong demand overseas.";
+    assert_eq!(simhash(para, &cfg), simhash(reflowed, &cfg));
+    assert!((simhash(para, &cfg) ^ simhash(unrelated, &cfg)).count_ones() > cfg.near_duplicate_bits);
+
+    let mut mem = TextMemory::default();
+    assert!(!mem.observe([para, para], &cfg), "repeats within one tick do not count");
+    assert!(mem.observe([para.trim_end_matches('.')], &cfg));
+    assert!(!mem.observe([unrelated, "", "Plant the tomatoes once the last frost has passed."], &cfg));
+    assert_eq!(mem.signatures.len(), 2);
+    assert!(!mem.observe([para], &cfg), "forgotten beyond recent_texts");
+
+    let off = FreezeCfg::default();
+    assert!(!TextMemory::default().observe([para], &off));
+    let ff = FreezeFlags { cross_tick_repeat: true, ..FreezeFlags::default() };
+    let mut state = ArbiterState::default();
+    state.bump(ff, false);
+    assert_eq!(state.hyst_rep, 1.0);
+}
@@ -4,7 +4,19 @@
 
 // nsc_arbiter_ffi ABI version.
 // Bumped when any exported function signature or struct layout changes.
-#define NSC_ARBITER_FFI_VERSION 1
+#define NSC_ARBITER_FFI_VERSION 15
+
+// Decision trace predicate bits (NscAction.trace_fired).
+// Margin for the predicate with bit (1 << i) is NscAction.trace_margins[i].
+#define NSC_TRACE_SLOTS 8
+#define NSC_PRED_HI_ENTROPY (1u << 0)
+#define NSC_PRED_LOW_SIM    (1u << 1)
+#define NSC_PRED_RULES_BAD  (1u << 2)
+#define NSC_PRED_GATE_BAD   (1u << 3)
+#define NSC_PRED_REP_CNT    (1u << 4)
+#define NSC_PRED_STALL_CNT  (1u << 5)
+#define NSC_PRED_ODDITY     (1u << 6)
+#define NSC_PRED_AI_TELL_CNT (1u << 7)
 
 #ifdef __cplusplus
 extern "C" {
@@ -29,19 +41,51 @@ typedef struct {
 typedef enum {
   NSC_ESC_NONE = 0,
   NSC_ESC_CRITIQUE_PASS = 1,
-  NSC_ESC_SECOND_LLM = 2
+  NSC_ESC_SECOND_LLM = 2,
+  NSC_ESC_PAUSE = 3
 } NscEscalation;
 
+typedef enum {
+  NSC_PAUSE_NONE = 0,
+  NSC_PAUSE_REPETITION = 1,
+  NSC_PAUSE_STALL = 2,
+  NSC_PAUSE_AI_TELL = 3
+} NscPauseReason;
+
+typedef enum {
+  NSC_HOLD_NONE = 0,
+  NSC_HOLD_COOLDOWN = 1,
+  NSC_HOLD_MIN_EVIDENCE_WEIGHT = 2,
+  NSC_HOLD_MIN_SOURCES = 3,
+  NSC_HOLD_MIN_DWELL = 4
+} NscHold;
+
+// A registry metric (nsc_arbiter_register_metric) reported on an action.
+typedef struct {
+  NscStr name;
+  float value;
+  uint8_t fired; // only set when tracing is enabled
+} NscMetric;
+
 typedef struct {
   NscStr intent_id;
   NscEscalation escalation;
+  NscPauseReason pause_reason; // NSC_PAUSE_NONE unless escalation == NSC_ESC_PAUSE
+  NscHold held; // why a warranted escalation came back as NSC_ESC_NONE
   float avg_entropy;
   float cosine_sim;
   float gate_shift;
   uint32_t rule_hits;
+  float oddity; // persona oddity in [0, 1]; 0 unless baselines are set for the intent
   uint8_t ff_rep_3p;
   uint8_t ff_stall;
   uint8_t ff_ai_tell;
+  uint8_t ff_cross_tick_repeat; // near-duplicate of a recent payload (nsc_arbiter_set_text_memory)
+  uint8_t has_trace; // 0 unless enabled via nsc_arbiter_set_trace()
+  uint32_t trace_fired; // NSC_PRED_* bitmask
+  float trace_margins[NSC_TRACE_SLOTS];
+  const NscMetric* metrics_ptr; // registered metrics, in registration order (NULL when none)
+  size_t metrics_len;
 } NscAction;
 
 typedef struct {
@@ -49,10 +93,24 @@ typedef struct {
   size_t actions_len;
   uint8_t* strings_ptr;
   size_t strings_len;
+  NscMetric* metrics_ptr;
+  size_t metrics_len;
 } NscActionArray;
 
 typedef struct { uint8_t* ptr; size_t len; } NscBytes;
 
+typedef struct {
+  float reliability; // [-1, 1]; 0 is neutral
+  uint64_t useful;
+  uint64_t not_useful;
+} NscSourceTrust;
+
+typedef struct {
+  uint64_t intents;
+  uint64_t evicted_capacity; // over max_intents_per_shard (least recently ticked first)
+  uint64_t evicted_idle;     // idle longer than idle_ttl
+} NscSupervisorStats;
+
 typedef struct {
   float tau_e;
   float tau_s;
@@ -61,8 +119,45 @@ typedef struct {
   float tau_gate;
   uint8_t hyst_disable;
   int32_t forced_rule_hits; // -1 means None
+  int32_t second_llm_min_ticks; // CritiquePass ticks before SecondLLM; -1 disables the rung
+  float second_llm_tau_e;
+  float second_llm_tau_s;
+  float second_llm_tau_gate;
+  uint8_t pause_priority; // 0 = pause first, 1 = critique first
+  uint8_t decay_kind; // 0 = none, 1 = exponential (decay_param = half-life), 2 = linear (decay_param = per time unit)
+  float decay_param;
+  uint32_t cooldown_ticks; // suppress ladder escalations for this many decisions after one fires
+  uint64_t cooldown_time;  // ... or for this many logical time units
+  float min_evidence_weight; // maturity gates; 0 disables each
+  uint32_t min_sources;
+  uint32_t min_dwell_ticks;
+  // Per-metric aggregation: 0 = weighted mean, 1 = weighted median, 2 = trimmed mean (agg_trim per tail),
+  // 3 = max, 4 = min, 5 = sum, 6 = any.
+  uint8_t agg_entropy;
+  uint8_t agg_cosine;
+  uint8_t agg_gate_shift;
+  uint8_t agg_rule_hits; // defaults to 3 (max)
+  float agg_trim;
+  float tau_oddity; // 1.0 disables
+  float oddity_z_thresh;
+  float oddity_alpha;
+  float oddity_mag_scale;
+  uint8_t learn_kind; // online baseline learning: 0 = off, 1 = Welford, 2 = EWMA (learn_half_life)
+  float learn_half_life;
+  uint32_t learn_warmup; // evidence records per intent before learned baselines are used
+  uint32_t tau_ai_tell; // act after this many ai_tell ticks; 0 disables
+  uint8_t ai_tell_action; // 0 = pause (NSC_PAUSE_AI_TELL), 1 = escalate
 } NscCfg;
 
+typedef struct {
+  float gate_shift_mu;
+  float gate_shift_sigma;
+  float entropy_mu;
+  float entropy_sigma;
+  float cos_dist_mu;
+  float cos_dist_sigma;
+} NscBaselines;
+
 // Returns the ABI version implemented by the linked library.
 uint32_t nsc_arbiter_ffi_version(void);
 
@@ -72,7 +167,54 @@ NscCfg nsc_arbiter_cfg_default(void);
 NscArbiterSupervisor* nsc_arbiter_supervisor_new(size_t shards, NscCfg cfg);
 void nsc_arbiter_supervisor_free(NscArbiterSupervisor* h);
 
+// Attach decision traces (fired bitmask + margins) to ingest results. Off by default.
+void nsc_arbiter_set_trace(NscArbiterSupervisor* h, uint8_t enabled);
+
+// Bound per-intent memory; 0 disables each limit. Enforced at the end of every ingest.
+void nsc_arbiter_set_eviction(NscArbiterSupervisor* h, size_t max_intents_per_shard, uint64_t idle_ttl);
+NscSupervisorStats nsc_arbiter_stats(NscArbiterSupervisor* h);
+
+// Per-intent persona baselines for oddity scoring (compared against NscCfg.tau_oddity).
+// nsc_arbiter_set_baselines() returns 0 on success, -1 on bad input.
+int32_t nsc_arbiter_set_baselines(NscArbiterSupervisor* h, NscStr intent_id, NscBaselines b);
+void nsc_arbiter_clear_baselines(NscArbiterSupervisor* h, NscStr intent_id);
+
+// Per-source weight profile. Once any is set, other sources get the unknown-source profile.
+// A replaced profile keeps its freeze eligibility. Returns 0 on success, -1 on bad input.
+int32_t nsc_arbiter_set_source_profile(NscArbiterSupervisor* h, NscStr source_id, float base_weight, float min_weight,
+                                       float max_weight);
+
+// Whether source_id's text is checked for freeze flags (default 1); flags and their hysteresis are
+// tracked per source. Returns 0 on success, -1 on bad input, -2 if the source has no profile.
+int32_t nsc_arbiter_set_source_freeze_eligible(NscArbiterSupervisor* h, NscStr source_id, uint8_t eligible);
+
+// Outcome feedback: was the latest escalation for intent_id useful? Moves the reliability of every
+// source behind it, which shifts that source's weight inside its profile band.
+// Returns the number of sources updated (0 if nothing to report), -1 on bad input.
+int32_t nsc_arbiter_report_outcome(NscArbiterSupervisor* h, NscStr intent_id, uint8_t useful);
+void nsc_arbiter_set_trust_rate(NscArbiterSupervisor* h, float rate); // default 0.1
+// Returns 0 and fills `out` (neutral if the source has no feedback yet), -1 on bad input.
+int32_t nsc_arbiter_source_trust(NscArbiterSupervisor* h, NscStr source_id, NscSourceTrust* out);
+
+// Declare a metric read from event scalars keyed `name`. `aggregation` uses the NscCfg agg_* codes
+// (`trim` for trimmed mean); direction 0 = higher is worse, 1 = lower is worse.
+// A fired metric counts toward NSC_ESC_CRITIQUE_PASS. Returns 0 on success, -1 on bad input.
+int32_t nsc_arbiter_register_metric(NscArbiterSupervisor* h, NscStr name, uint8_t aggregation, float trim,
+                                    uint8_t direction, float threshold);
+
+// Replace the phrases that set the ai_tell freeze flag; case_fold 0 = exact,
+// 1 = ASCII case-insensitive (default), 2 = Unicode. Returns 0 on success, -1 on bad input.
+int32_t nsc_arbiter_set_ai_phrases(NscArbiterSupervisor* h, const NscStr* phrases_ptr, size_t phrases_len,
+                                   uint8_t case_fold);
+
+// Remember the last recent_texts payload signatures per (intent, source) (0 disables) and set
+// ff_cross_tick_repeat for payloads within near_duplicate_bits of one. Returns 0, or -1 on bad input.
+int32_t nsc_arbiter_set_text_memory(NscArbiterSupervisor* h, uint32_t recent_texts, uint32_t near_duplicate_bits);
+
 NscActionArray nsc_arbiter_ingest(NscArbiterSupervisor* h, const NscEvent* events_ptr, size_t events_len);
+// Same as nsc_arbiter_ingest() at caller-supplied logical time `now`.
+// nsc_arbiter_ingest() advances the logical clock by one unit per call instead.
+NscActionArray nsc_arbiter_ingest_at(NscArbiterSupervisor* h, const NscEvent* events_ptr, size_t events_len, uint64_t now);
 void nsc_arbiter_actions_free(NscActionArray arr);
 
 // Snapshot bytes are a versioned binary format (magic+version prefix).
@@ -3,14 +3,21 @@
 use std::collections::HashMap;
 use std::ptr;
 
-use nsc_arbiter_core::ArbiterCfg;
-use nsc_arbiter_supervisor::{ArbiterSupervisor, BasicEvidenceBuilder, SignalEvent};
+use nsc_arbiter_core::{
+    AiTellAction, Aggregation, AggregationCfg, ArbiterCfg, ArbiterState, CaseFold, BaselineEstimator, BaselineLearning, Direction, Escalation,
+    EscalationTier, Hold, HystDecay, LearnedBaselines, MetricRegistry, MetricSpec, OddityParams, PausePriority,
+    PauseReason, PersonaBaselines, RunningStat, SourceHyst, SourceProfile, SourceTrust, TextMemory,
+};
+use nsc_arbiter_supervisor::{ArbiterSupervisor, BasicEvidenceBuilder, EvictionCfg, SignalEvent};
 use nsc_arbiter_supervisor::supervisor::SupervisorSnapshot;
 
 /// FFI ABI version for nsc_arbiter_ffi.
 ///
 /// Bump this when any `#[repr(C)]` struct layout or exported function signature changes.
-pub const NSC_ARBITER_FFI_VERSION: u32 = 1;
+pub const NSC_ARBITER_FFI_VERSION: u32 = 15;
+
+/// Number of margin slots in `NscAction::trace_margins`, indexed by predicate bit position.
+pub const NSC_TRACE_SLOTS: usize = 8;
 
 #[no_mangle]
 pub extern "C" fn nsc_arbiter_ffi_version() -> u32 {
@@ -19,7 +26,7 @@ pub extern "C" fn nsc_arbiter_ffi_version() -> u32 {
 
 // Snapshot wire format identification.
 const SNAP_MAGIC: u32 = 0x3142_5241; // "ARB1" little-endian
-const SNAP_VERSION: u32 = 1;
+const SNAP_VERSION: u32 = 3;
 
 /// Opaque handle exposed over FFI.
 #[repr(C)]
@@ -80,6 +87,38 @@ pub enum NscEscalation {
     None = 0,
     CritiquePass = 1,
     SecondLLM = 2,
+    Pause = 3,
+}
+
+/// Pause reason as a C-friendly enum. `None` unless the escalation is `Pause`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NscPauseReason {
+    None = 0,
+    Repetition = 1,
+    Stall = 2,
+    AiTell = 3,
+}
+
+/// Why a warranted escalation was held back (C-friendly).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NscHold {
+    None = 0,
+    Cooldown = 1,
+    MinEvidenceWeight = 2,
+    MinSources = 3,
+    MinDwell = 4,
+}
+
+/// One registry metric on an action (see `nsc_arbiter_register_metric`).
+/// `name` points into the action array's string buffer.
+#[repr(C)]
+pub struct NscMetric {
+    pub name: NscStr,
+    pub value: f32,
+    /// 1 if the metric fired; only known when tracing is enabled, 0 otherwise.
+    pub fired: u8,
 }
 
 /// Output action.
@@ -88,17 +127,33 @@ pub enum NscEscalation {
 pub struct NscAction {
     pub intent_id: NscStr,
     pub escalation: NscEscalation,
+    pub pause_reason: NscPauseReason,
+    pub held: NscHold,
 
     /// Telemetry (always populated by current supervisor)
     pub avg_entropy: f32,
     pub cosine_sim: f32,
     pub gate_shift: f32,
     pub rule_hits: u32,
+    /// Persona oddity in [0, 1]; 0 unless baselines are set for the intent.
+    pub oddity: f32,
 
     /// Freeze flags
     pub ff_rep_3p: u8,
     pub ff_stall: u8,
     pub ff_ai_tell: u8,
+    pub ff_cross_tick_repeat: u8,
+
+    /// Decision trace (see `nsc_arbiter_set_trace`); zeroed when `has_trace == 0`.
+    pub has_trace: u8,
+    /// Bitmask of fired predicates (`NSC_PRED_*` in the C header).
+    pub trace_fired: u32,
+    /// Margin per predicate, slot `i` belongs to the predicate with bit `1 << i`.
+    pub trace_margins: [f32; NSC_TRACE_SLOTS],
+
+    /// Registry metrics reported for this intent, in registration order.
+    pub metrics_ptr: *const NscMetric,
+    pub metrics_len: usize,
 }
 
 /// Owned array returned over FFI.
@@ -110,6 +165,10 @@ pub struct NscActionArray {
     // backing storage for strings (one blob) so intent_id pointers stay valid
     pub strings_ptr: *mut u8,
     pub strings_len: usize,
+
+    // backing storage for every action's metrics (one block)
+    pub metrics_ptr: *mut NscMetric,
+    pub metrics_len: usize,
 }
 
 /// Owned byte buffer (for snapshot).
@@ -127,6 +186,25 @@ pub struct NscRestoreStats {
     pub rc: i32,
 }
 
+/// Learned reliability of one source (see `nsc_arbiter_report_outcome`).
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct NscSourceTrust {
+    /// In [-1, 1]; 0 is neutral.
+    pub reliability: f32,
+    pub useful: u64,
+    pub not_useful: u64,
+}
+
+/// Supervisor counters (see `nsc_arbiter_stats`).
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct NscSupervisorStats {
+    pub intents: u64,
+    pub evicted_capacity: u64,
+    pub evicted_idle: u64,
+}
+
 /// Supervisor cfg for FFI (keep it minimal).
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -138,6 +216,65 @@ pub struct NscCfg {
     pub tau_gate: f32,
     pub hyst_disable: u8,
     pub forced_rule_hits: i32, // -1 means None
+
+    /// Consecutive CritiquePass ticks before promotion to SecondLLM; -1 disables the rung.
+    pub second_llm_min_ticks: i32,
+    pub second_llm_tau_e: f32,
+    pub second_llm_tau_s: f32,
+    pub second_llm_tau_gate: f32,
+
+    /// 0 = pause first, 1 = critique first.
+    pub pause_priority: u8,
+
+    /// Hysteresis decay: 0 = none, 1 = exponential (`decay_param` = half-life),
+    /// 2 = linear (`decay_param` = amount per time unit).
+    pub decay_kind: u8,
+    pub decay_param: f32,
+    pub cooldown_ticks: u32,
+    pub cooldown_time: u64,
+
+    /// Maturity gates (0 disables each).
+    pub min_evidence_weight: f32,
+    pub min_sources: u32,
+    pub min_dwell_ticks: u32,
+
+    /// Per-metric aggregation: 0 = weighted mean, 1 = weighted median, 2 = trimmed mean
+    /// (trimming `agg_trim` of the weight from each tail), 3 = max, 4 = min, 5 = sum, 6 = any.
+    pub agg_entropy: u8,
+    pub agg_cosine: u8,
+    pub agg_gate_shift: u8,
+    /// Defaults to 3 (max).
+    pub agg_rule_hits: u8,
+    pub agg_trim: f32,
+
+    /// Oddity threshold (1.0 disables) and scoring parameters.
+    pub tau_oddity: f32,
+    pub oddity_z_thresh: f32,
+    pub oddity_alpha: f32,
+    pub oddity_mag_scale: f32,
+
+    /// Online baseline learning: 0 = off, 1 = Welford, 2 = EWMA (`learn_half_life`).
+    /// Oddity stays 0 for the first `learn_warmup` evidence records of an intent.
+    pub learn_kind: u8,
+    pub learn_half_life: f32,
+    pub learn_warmup: u32,
+
+    /// Act after this many `ai_tell` ticks (0 disables): `ai_tell_action` 0 = pause,
+    /// 1 = escalate. Phrases are set with `nsc_arbiter_set_ai_phrases`.
+    pub tau_ai_tell: u32,
+    pub ai_tell_action: u8,
+}
+
+/// Persona baselines for one intent (see `nsc_arbiter_set_baselines`).
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct NscBaselines {
+    pub gate_shift_mu: f32,
+    pub gate_shift_sigma: f32,
+    pub entropy_mu: f32,
+    pub entropy_sigma: f32,
+    pub cos_dist_mu: f32,
+    pub cos_dist_sigma: f32,
 }
 
 #[no_mangle]
@@ -151,10 +288,71 @@ pub extern "C" fn nsc_arbiter_cfg_default() -> NscCfg {
         tau_gate: d.tau_gate,
         hyst_disable: if d.hyst_disable { 1 } else { 0 },
         forced_rule_hits: d.forced_rule_hits.map(|v| v as i32).unwrap_or(-1),
+        second_llm_min_ticks: -1,
+        second_llm_tau_e: d.tau_e,
+        second_llm_tau_s: d.tau_s,
+        second_llm_tau_gate: d.tau_gate,
+        pause_priority: if d.pause_priority == PausePriority::CritiqueFirst { 1 } else { 0 },
+        decay_kind: 0,
+        decay_param: 0.0,
+        cooldown_ticks: d.cooldown_ticks,
+        cooldown_time: d.cooldown_time,
+        min_evidence_weight: d.min_evidence_weight,
+        min_sources: d.min_sources,
+        min_dwell_ticks: d.min_dwell_ticks,
+        agg_entropy: agg_to_ffi(d.aggregation.avg_entropy),
+        agg_cosine: agg_to_ffi(d.aggregation.cosine_sim),
+        agg_gate_shift: agg_to_ffi(d.aggregation.gate_shift),
+        agg_rule_hits: agg_to_ffi(d.aggregation.rule_hits),
+        agg_trim: 0.1,
+        tau_oddity: d.tau_oddity,
+        oddity_z_thresh: d.oddity.z_thresh,
+        oddity_alpha: d.oddity.alpha,
+        oddity_mag_scale: d.oddity.mag_scale,
+        learn_kind: 0,
+        learn_half_life: 0.0,
+        learn_warmup: 0,
+        tau_ai_tell: d.tau_ai_tell,
+        ai_tell_action: 0,
+    }
+}
+
+fn agg_to_ffi(a: Aggregation) -> u8 {
+    match a {
+        Aggregation::WeightedMean => 0,
+        Aggregation::WeightedMedian => 1,
+        Aggregation::TrimmedMean { .. } => 2,
+        Aggregation::Max => 3,
+        Aggregation::Min => 4,
+        Aggregation::Sum => 5,
+        Aggregation::Any => 6,
+    }
+}
+
+fn agg_from_ffi(code: u8, trim: f32) -> Aggregation {
+    match code {
+        1 => Aggregation::WeightedMedian,
+        2 => Aggregation::TrimmedMean { trim },
+        3 => Aggregation::Max,
+        4 => Aggregation::Min,
+        5 => Aggregation::Sum,
+        6 => Aggregation::Any,
+        _ => Aggregation::WeightedMean,
     }
 }
 
 fn cfg_from_ffi(c: NscCfg) -> ArbiterCfg {
+    let mut ladder = Vec::new();
+    if c.second_llm_min_ticks >= 0 {
+        ladder.push(EscalationTier {
+            escalation: Escalation::SecondLLM,
+            tau_e: c.second_llm_tau_e,
+            tau_s: c.second_llm_tau_s,
+            tau_gate: c.second_llm_tau_gate,
+            min_ticks_below: c.second_llm_min_ticks as u32,
+        });
+    }
+
     ArbiterCfg {
         tau_e: c.tau_e,
         tau_s: c.tau_s,
@@ -163,14 +361,68 @@ fn cfg_from_ffi(c: NscCfg) -> ArbiterCfg {
         tau_gate: c.tau_gate,
         hyst_disable: c.hyst_disable != 0,
         forced_rule_hits: if c.forced_rule_hits < 0 { None } else { Some(c.forced_rule_hits as u32) },
+        ladder,
+        pause_priority: if c.pause_priority != 0 { PausePriority::CritiqueFirst } else { PausePriority::PauseFirst },
+        hyst_decay: match c.decay_kind {
+            1 => HystDecay::Exponential { half_life: c.decay_param },
+            2 => HystDecay::Linear { per_unit: c.decay_param },
+            _ => HystDecay::None,
+        },
+        cooldown_ticks: c.cooldown_ticks,
+        cooldown_time: c.cooldown_time,
+        min_evidence_weight: c.min_evidence_weight,
+        min_sources: c.min_sources,
+        min_dwell_ticks: c.min_dwell_ticks,
+        aggregation: AggregationCfg {
+            avg_entropy: agg_from_ffi(c.agg_entropy, c.agg_trim),
+            cosine_sim: agg_from_ffi(c.agg_cosine, c.agg_trim),
+            gate_shift: agg_from_ffi(c.agg_gate_shift, c.agg_trim),
+            rule_hits: agg_from_ffi(c.agg_rule_hits, c.agg_trim),
+        },
+        // Registered separately via `nsc_arbiter_register_metric`.
+        metrics: MetricRegistry::new(),
+        tau_oddity: c.tau_oddity,
+        oddity: OddityParams {
+            z_thresh: c.oddity_z_thresh,
+            alpha: c.oddity_alpha,
+            mag_scale: c.oddity_mag_scale,
+        },
+        baseline_learning: match c.learn_kind {
+            1 => Some(BaselineEstimator::Welford),
+            2 => Some(BaselineEstimator::Ewma { half_life: c.learn_half_life }),
+            _ => None,
+        }
+        .map(|estimator| BaselineLearning { estimator, warmup: c.learn_warmup as u64 }),
+        tau_ai_tell: c.tau_ai_tell,
+        ai_tell_action: if c.ai_tell_action != 0 { AiTellAction::Escalate } else { AiTellAction::Pause },
+        // Set separately via `nsc_arbiter_set_ai_phrases`.
+        freeze: Default::default(),
     }
 }
 
-fn esc_to_ffi(e: nsc_arbiter_core::Escalation) -> NscEscalation {
+fn esc_to_ffi(e: Escalation) -> (NscEscalation, NscPauseReason) {
     match e {
-        nsc_arbiter_core::Escalation::None => NscEscalation::None,
-        nsc_arbiter_core::Escalation::CritiquePass => NscEscalation::CritiquePass,
-        nsc_arbiter_core::Escalation::SecondLLM => NscEscalation::SecondLLM,
+        Escalation::None => (NscEscalation::None, NscPauseReason::None),
+        Escalation::CritiquePass => (NscEscalation::CritiquePass, NscPauseReason::None),
+        Escalation::SecondLLM => (NscEscalation::SecondLLM, NscPauseReason::None),
+        Escalation::Pause { reason } => {
+            let r = match reason {
+                PauseReason::Repetition => NscPauseReason::Repetition,
+                PauseReason::Stall => NscPauseReason::Stall,
+                PauseReason::AiTell => NscPauseReason::AiTell,
+            };
+            (NscEscalation::Pause, r)
+        }
+    }
+}
+
+fn hold_to_ffi(h: Option<Hold>) -> NscHold {
+    match h {
+        None => NscHold::None,
+        Some(Hold::Cooldown) => NscHold::Cooldown,
+        Some(Hold::MinEvidenceWeight) => NscHold::MinEvidenceWeight,
+        Some(Hold::MinSources) => NscHold::MinSources,
+        Some(Hold::MinDwell) => NscHold::MinDwell,
     }
 }
 
@@ -197,15 +449,266 @@ pub unsafe extern "C" fn nsc_arbiter_supervisor_free(h: *mut NscArbiterSuperviso
     }
 }
 
+/// Enable (`enabled != 0`) or disable decision traces on returned actions.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_trace(h: *mut NscArbiterSupervisor, enabled: u8) {
+    if !h.is_null() {
+        (*h).inner.set_trace(enabled != 0);
+    }
+}
+
+/// Set persona baselines for `intent_id`, enabling oddity scoring for it.
+/// Returns 0 on success, -1 on a null handle or invalid intent id.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_baselines(h: *mut NscArbiterSupervisor, intent_id: NscStr, b: NscBaselines) -> i32 {
+    if h.is_null() {
+        return -1;
+    }
+    let intent_id = match intent_id.as_str() { Some(s) => s, None => return -1 };
+    let baselines = PersonaBaselines {
+        gate_shift_mu: b.gate_shift_mu,
+        gate_shift_sigma: b.gate_shift_sigma,
+        entropy_mu: b.entropy_mu,
+        entropy_sigma: b.entropy_sigma,
+        cos_dist_mu: b.cos_dist_mu,
+        cos_dist_sigma: b.cos_dist_sigma,
+    };
+    (*h).inner.set_baselines(intent_id, baselines);
+    0
+}
+
+/// Remove persona baselines for `intent_id`.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_clear_baselines(h: *mut NscArbiterSupervisor, intent_id: NscStr) {
+    if h.is_null() {
+        return;
+    }
+    if let Some(intent_id) = intent_id.as_str() {
+        (*h).inner.clear_baselines(intent_id);
+    }
+}
+
+/// Set (or replace) the weight profile of one source. Once any profile is set, sources
+/// without one get the built-in unknown-source profile. A replaced profile keeps its
+/// freeze eligibility (see `nsc_arbiter_set_source_freeze_eligible`).
+/// Returns 0 on success, -1 on a null handle or invalid source id.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_source_profile(
+    h: *mut NscArbiterSupervisor,
+    source_id: NscStr,
+    base_weight: f32,
+    min_weight: f32,
+    max_weight: f32,
+) -> i32 {
+    if h.is_null() {
+        return -1;
+    }
+    let source_id = match source_id.as_str() { Some(s) => s, None => return -1 };
+    let eligible = (*h).inner.source_profile(source_id).is_none_or(|p| p.freeze_eligible);
+    let profile = SourceProfile::new(base_weight, min_weight, max_weight).with_freeze_eligible(eligible);
+    (*h).inner.set_source_profile(source_id, profile);
+    0
+}
+
+/// Whether text from `source_id` is checked for freeze flags (default 1). Freeze flags
+/// and their hysteresis are tracked per source, so an ineligible source can never
+/// trigger a pause. Returns 0 on success, -1 on a null handle or invalid source id,
+/// -2 if the source has no profile (set one with `nsc_arbiter_set_source_profile`).
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_source_freeze_eligible(
+    h: *mut NscArbiterSupervisor,
+    source_id: NscStr,
+    eligible: u8,
+) -> i32 {
+    if h.is_null() {
+        return -1;
+    }
+    let source_id = match source_id.as_str() { Some(s) => s, None => return -1 };
+    let profile = match (*h).inner.source_profile(source_id) {
+        Some(p) => p.clone().with_freeze_eligible(eligible != 0),
+        None => return -2,
+    };
+    (*h).inner.set_source_profile(source_id, profile);
+    0
+}
+
+/// Bound per-intent memory: at most `max_intents_per_shard` intents per shard (least recently
+/// ticked evicted first) and evict intents idle for more than `idle_ttl` logical time units.
+/// 0 disables each limit.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_eviction(h: *mut NscArbiterSupervisor, max_intents_per_shard: usize, idle_ttl: u64) {
+    if !h.is_null() {
+        (*h).inner.set_eviction(EvictionCfg { max_intents_per_shard, idle_ttl });
+    }
+}
+
+/// Current intent count and eviction counters (all zero for a null handle).
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_stats(h: *mut NscArbiterSupervisor) -> NscSupervisorStats {
+    if h.is_null() {
+        return NscSupervisorStats::default();
+    }
+    let s = (*h).inner.stats();
+    NscSupervisorStats { intents: s.intents as u64, evicted_capacity: s.evicted_capacity, evicted_idle: s.evicted_idle }
+}
+
+/// Set the step size of each outcome report (0..=1, default 0.1).
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_trust_rate(h: *mut NscArbiterSupervisor, rate: f32) {
+    if !h.is_null() {
+        (*h).inner.set_trust_rate(rate);
+    }
+}
+
+/// Report whether the latest escalation for `intent_id` was useful (`useful != 0`).
+/// Returns the number of sources whose reliability moved, or -1 on bad input.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_report_outcome(h: *mut NscArbiterSupervisor, intent_id: NscStr, useful: u8) -> i32 {
+    if h.is_null() {
+        return -1;
+    }
+    let intent_id = match intent_id.as_str() { Some(s) => s, None => return -1 };
+    (*h).inner.report_outcome(intent_id, useful != 0) as i32
+}
+
+/// Read the learned reliability of `source_id` into `out` (neutral if it has no feedback yet).
+/// Returns 0 on success, -1 on bad input.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_source_trust(
+    h: *mut NscArbiterSupervisor,
+    source_id: NscStr,
+    out: *mut NscSourceTrust,
+) -> i32 {
+    if h.is_null() || out.is_null() {
+        return -1;
+    }
+    let source_id = match source_id.as_str() { Some(s) => s, None => return -1 };
+    let t = (*h)
+        .inner
+        .source_trust()
+        .into_iter()
+        .find(|(id, _)| id == source_id)
+        .map(|(_, t)| t)
+        .unwrap_or_default();
+    *out = NscSourceTrust { reliability: t.reliability, useful: t.useful, not_useful: t.not_useful };
+    0
+}
+
+/// Declare a named metric read from event scalars with key `name`.
+///
+/// `aggregation` uses the `NscCfg::agg_*` codes (`trim` applies to trimmed mean);
+/// `direction` is 0 = higher is worse, 1 = lower is worse. A metric that fires counts
+/// toward `CritiquePass` like the built-in predicates. Re-registering a name replaces it.
+/// Returns 0 on success, -1 on a null handle or invalid name.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_register_metric(
+    h: *mut NscArbiterSupervisor,
+    name: NscStr,
+    aggregation: u8,
+    trim: f32,
+    direction: u8,
+    threshold: f32,
+) -> i32 {
+    if h.is_null() {
+        return -1;
+    }
+    let name = match name.as_str() {
+        Some(n) if !n.is_empty() => n,
+        _ => return -1,
+    };
+    let direction = if direction != 0 { Direction::LowerIsWorse } else { Direction::HigherIsWorse };
+    let spec = MetricSpec::new(name, agg_from_ffi(aggregation, trim), direction, threshold);
+    (*h).inner.cfg_mut().metrics.register(spec);
+    0
+}
+
+/// Replace the phrases that set the `ai_tell` freeze flag. `case_fold` is 0 = exact,
+/// 1 = ASCII case-insensitive (default), 2 = Unicode case-insensitive.
+/// Returns 0 on success, -1 on a null handle or a phrase that is not UTF-8.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_ai_phrases(
+    h: *mut NscArbiterSupervisor,
+    phrases_ptr: *const NscStr,
+    phrases_len: usize,
+    case_fold: u8,
+) -> i32 {
+    if h.is_null() || (phrases_ptr.is_null() && phrases_len > 0) {
+        return -1;
+    }
+    let raw = if phrases_len == 0 { &[][..] } else { std::slice::from_raw_parts(phrases_ptr, phrases_len) };
+    let mut phrases = Vec::with_capacity(raw.len());
+    for p in raw {
+        match p.as_str() {
+            Some(s) => phrases.push(s.to_string()),
+            None => return -1,
+        }
+    }
+    let freeze = &mut (*h).inner.cfg_mut().freeze;
+    freeze.ai_phrases = phrases;
+    freeze.case_fold = match case_fold {
+        0 => CaseFold::Exact,
+        2 => CaseFold::Unicode,
+        _ => CaseFold::Ascii,
+    };
+    0
+}
+
+/// Remember the last `recent_texts` payload signatures per (intent, source) (0 disables) and set
+/// `ff_cross_tick_repeat` when a payload is within `near_duplicate_bits` of one of them.
+/// Returns 0 on success, -1 on a null handle.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_set_text_memory(
+    h: *mut NscArbiterSupervisor,
+    recent_texts: u32,
+    near_duplicate_bits: u32,
+) -> i32 {
+    if h.is_null() {
+        return -1;
+    }
+    let freeze = &mut (*h).inner.cfg_mut().freeze;
+    freeze.recent_texts = recent_texts as usize;
+    freeze.near_duplicate_bits = near_duplicate_bits;
+    0
+}
+
 /// Ingest events. Returns an owned action array (must be freed with `nsc_arbiter_actions_free`).
+///
+/// Each call advances the supervisor's logical clock by one unit.
 #[no_mangle]
 pub unsafe extern "C" fn nsc_arbiter_ingest(
     h: *mut NscArbiterSupervisor,
     events_ptr: *const NscEvent,
     events_len: usize,
+) -> NscActionArray {
+    ingest_impl(h, events_ptr, events_len, None)
+}
+
+/// Like `nsc_arbiter_ingest`, at caller-supplied logical time `now`.
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_ingest_at(
+    h: *mut NscArbiterSupervisor,
+    events_ptr: *const NscEvent,
+    events_len: usize,
+    now: u64,
+) -> NscActionArray {
+    ingest_impl(h, events_ptr, events_len, Some(now))
+}
+
+unsafe fn ingest_impl(
+    h: *mut NscArbiterSupervisor,
+    events_ptr: *const NscEvent,
+    events_len: usize,
+    now: Option<u64>,
 ) -> NscActionArray {
     if h.is_null() || events_ptr.is_null() || events_len == 0 {
-        return NscActionArray { actions_ptr: ptr::null_mut(), actions_len: 0, strings_ptr: ptr::null_mut(), strings_len: 0 };
+        return NscActionArray {
+            actions_ptr: ptr::null_mut(),
+            actions_len: 0,
+            strings_ptr: ptr::null_mut(),
+            strings_len: 0,
+            metrics_ptr: ptr::null_mut(),
+            metrics_len: 0,
+        };
     }
 
     let handle = &mut *h;
@@ -244,12 +747,19 @@ pub unsafe extern "C" fn nsc_arbiter_ingest(
         rust_events.push(se);
     }
 
-    let actions = handle.inner.ingest(&handle.builder, &rust_events);
+    let actions = match now {
+        Some(now) => handle.inner.ingest_at(&handle.builder, &rust_events, now),
+        None => handle.inner.ingest(&handle.builder, &rust_events),
+    };
 
     // Build a single backing blob for intent_id strings
     let mut strings: Vec<u8> = Vec::new();
     let mut out: Vec<NscAction> = Vec::with_capacity(actions.len());
     let mut offsets: Vec<(usize, usize)> = Vec::with_capacity(actions.len());
+    let mut metrics: Vec<NscMetric> = Vec::new();
+    // per action: first metric index; per metric: name offset in `strings`
+    let mut metric_starts: Vec<usize> = Vec::with_capacity(actions.len());
+    let mut metric_names: Vec<usize> = Vec::new();
 
     for a in actions {
         let start = strings.len();
@@ -257,25 +767,62 @@ pub unsafe extern "C" fn nsc_arbiter_ingest(
         let len = strings.len() - start;
         offsets.push((start, len));
 
-        let (ff_rep_3p, ff_stall, ff_ai_tell) = if let Some(ff) = a.freeze_flags {
-            (ff.rep_3p as u8, ff.stall as u8, ff.ai_tell as u8)
+        let (ff_rep_3p, ff_stall, ff_ai_tell, ff_cross_tick_repeat) = if let Some(ff) = a.freeze_flags {
+            (ff.rep_3p as u8, ff.stall as u8, ff.ai_tell as u8, ff.cross_tick_repeat as u8)
         } else {
-            (0, 0, 0)
+            (0, 0, 0, 0)
         };
 
         let u = a.uncertainty.unwrap_or_default();
+        let (escalation, pause_reason) = esc_to_ffi(a.escalation);
+
+        metric_starts.push(metrics.len());
+        for m in &u.metrics {
+            metric_names.push(strings.len());
+            strings.extend_from_slice(m.name.as_bytes());
+            let fired = a
+                .trace
+                .as_ref()
+                .and_then(|t| t.metrics.iter().find(|mt| mt.name == m.name))
+                .is_some_and(|mt| mt.fired);
+            metrics.push(NscMetric {
+                name: NscStr { ptr: ptr::null(), len: m.name.len() },
+                value: m.value,
+                fired: fired as u8,
+            });
+        }
+
+        let mut trace_margins = [0.0_f32; NSC_TRACE_SLOTS];
+        let (has_trace, trace_fired) = match &a.trace {
+            Some(t) => {
+                for p in &t.predicates {
+                    trace_margins[p.predicate as usize] = p.margin;
+                }
+                (1, t.fired_mask())
+            }
+            None => (0, 0),
+        };
 
         out.push(NscAction {
             // fixed up after we pin the backing string blob
             intent_id: NscStr { ptr: ptr::null(), len },
-            escalation: esc_to_ffi(a.escalation),
+            escalation,
+            pause_reason,
+            held: hold_to_ffi(a.held),
             avg_entropy: u.avg_entropy,
             cosine_sim: u.cosine_sim,
             gate_shift: u.gate_shift,
             rule_hits: u.rule_hits,
+            oddity: u.oddity,
             ff_rep_3p,
             ff_stall,
             ff_ai_tell,
+            ff_cross_tick_repeat,
+            has_trace,
+            trace_fired,
+            trace_margins,
+            metrics_ptr: ptr::null(),
+            metrics_len: u.metrics.len(),
         });
     }
 
@@ -288,19 +835,32 @@ pub unsafe extern "C" fn nsc_arbiter_ingest(
     let actions_ptr = out_box.as_mut_ptr();
     let actions_len = out_box.len();
 
-    for (act, (off, _len)) in out_box.iter_mut().zip(offsets.into_iter()) {
+    let mut metrics_box = metrics.into_boxed_slice();
+    for (m, off) in metrics_box.iter_mut().zip(metric_names) {
+        m.name.ptr = strings_ptr.add(off);
+    }
+    let metrics_ptr = metrics_box.as_mut_ptr();
+    let metrics_len = metrics_box.len();
+
+    for ((act, (off, _len)), first) in out_box.iter_mut().zip(offsets).zip(metric_starts) {
         act.intent_id.ptr = strings_ptr.add(off);
+        if act.metrics_len > 0 {
+            act.metrics_ptr = metrics_ptr.add(first);
+        }
     }
 
     // Leak boxes to caller; freed by nsc_arbiter_actions_free
     std::mem::forget(strings_box);
     std::mem::forget(out_box);
+    std::mem::forget(metrics_box);
 
     NscActionArray {
         actions_ptr,
         actions_len,
         strings_ptr,
         strings_len,
+        metrics_ptr,
+        metrics_len,
     }
 }
 
@@ -314,12 +874,41 @@ pub unsafe extern "C" fn nsc_arbiter_actions_free(arr: NscActionArray) {
         let slice_ptr = std::ptr::slice_from_raw_parts_mut(arr.strings_ptr, arr.strings_len);
         drop(Box::from_raw(slice_ptr));
     }
+    if !arr.metrics_ptr.is_null() {
+        let slice_ptr = std::ptr::slice_from_raw_parts_mut(arr.metrics_ptr, arr.metrics_len);
+        drop(Box::from_raw(slice_ptr));
+    }
 }
 
-/// Snapshot format (binary):
-/// [u32 magic = "ARB1"][u32 version = 1][u32 count]
+/// Snapshot format (binary, little-endian):
+/// [u32 magic = "ARB1"][u32 version = 3][u32 count][u64 clock]
 /// repeated count times:
-///   [u32 strlen][bytes...][u32 hyst_rep][u32 hyst_stall]
+///   [u32 strlen][bytes...][u32 statelen][state bytes...]
+///
+/// State bytes, in order:
+///   [f32 hyst_rep][f32 hyst_stall][u32 tier][u32 tier_ticks]
+///   [u64 last_ts][u64 last_escalation_ts][u32 ticks_since_escalation]
+///   [u32 over_ticks]
+///   [u8 has_learned] then, if 1, three of [u64 n][f32 mean][f32 var]
+///     (gate_shift, entropy, cosine distance)
+///   [f32 hyst_ai_tell]
+///   [u32 source_count] then repeated
+///     [u32 strlen][source bytes...][f32 rep][f32 stall][f32 ai_tell]
+/// where all-ones marks an absent optional value. Decoders default any trailing
+/// fields missing from a shorter record.
+///
+/// Optional trailing section (absent means no learned source trust):
+///   [u32 trust_count] then repeated
+///   [u32 strlen][bytes...][f32 reliability][u64 useful][u64 not_useful]
+///
+/// Optional trailing section after it (absent means no text memory):
+///   [u32 memory_count] then repeated
+///   [u32 strlen][intent bytes...][u32 strlen][source bytes...]
+///   [u32 sig_count][u64 signature]*sig_count (oldest first)
+///
+/// Version 2 (u32 hysteresis counters, no clock) and version 1
+/// (`[u32 strlen][bytes...][u32 hyst_rep][u32 hyst_stall]`) are still accepted
+/// by `nsc_arbiter_restore`.
 #[no_mangle]
 pub unsafe extern "C" fn nsc_arbiter_snapshot(h: *mut NscArbiterSupervisor) -> NscBytes {
     if h.is_null() {
@@ -332,16 +921,38 @@ pub unsafe extern "C" fn nsc_arbiter_snapshot(h: *mut NscArbiterSupervisor) -> N
     buf.extend_from_slice(&SNAP_MAGIC.to_le_bytes());
     buf.extend_from_slice(&SNAP_VERSION.to_le_bytes());
     buf.extend_from_slice(&(snap.states.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&snap.clock.to_le_bytes());
 
     for (id, st) in snap.states {
         let idb = id.as_bytes();
         buf.extend_from_slice(&(idb.len() as u32).to_le_bytes());
         buf.extend_from_slice(idb);
 
-        // This assumes ArbiterState has these fields (per your extracted core/supervisor usage).
-        // If your ArbiterState differs, adjust here to match its actual shape.
-        buf.extend_from_slice(&(st.hyst_rep as u32).to_le_bytes());
-        buf.extend_from_slice(&(st.hyst_stall as u32).to_le_bytes());
+        let state = encode_state(&st);
+        buf.extend_from_slice(&(state.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&state);
+    }
+
+    buf.extend_from_slice(&(snap.source_trust.len() as u32).to_le_bytes());
+    for (id, t) in snap.source_trust {
+        let idb = id.as_bytes();
+        buf.extend_from_slice(&(idb.len() as u32).to_le_bytes());
+        buf.extend_from_slice(idb);
+        buf.extend_from_slice(&t.reliability.to_le_bytes());
+        buf.extend_from_slice(&t.useful.to_le_bytes());
+        buf.extend_from_slice(&t.not_useful.to_le_bytes());
+    }
+
+    buf.extend_from_slice(&(snap.text_memory.len() as u32).to_le_bytes());
+    for (id, source_id, m) in snap.text_memory {
+        for s in [id.as_bytes(), source_id.as_bytes()] {
+            buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            buf.extend_from_slice(s);
+        }
+        buf.extend_from_slice(&(m.signatures.len() as u32).to_le_bytes());
+        for sig in m.signatures {
+            buf.extend_from_slice(&sig.to_le_bytes());
+        }
     }
 
     let mut boxed = buf.into_boxed_slice();
@@ -352,70 +963,193 @@ pub unsafe extern "C" fn nsc_arbiter_snapshot(h: *mut NscArbiterSupervisor) -> N
     NscBytes { ptr, len }
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn nsc_arbiter_bytes_free(b: NscBytes) {
-    if !b.ptr.is_null() {
-        let slice_ptr = std::ptr::slice_from_raw_parts_mut(b.ptr, b.len);
-        drop(Box::from_raw(slice_ptr));
+fn encode_state(st: &ArbiterState) -> Vec<u8> {
+    let mut out = Vec::with_capacity(96);
+    out.extend_from_slice(&st.hyst_rep.to_le_bytes());
+    out.extend_from_slice(&st.hyst_stall.to_le_bytes());
+    out.extend_from_slice(&st.tier.to_le_bytes());
+    out.extend_from_slice(&st.tier_ticks.to_le_bytes());
+    out.extend_from_slice(&st.last_ts.unwrap_or(u64::MAX).to_le_bytes());
+    out.extend_from_slice(&st.last_escalation_ts.unwrap_or(u64::MAX).to_le_bytes());
+    out.extend_from_slice(&st.ticks_since_escalation.unwrap_or(u32::MAX).to_le_bytes());
+    out.extend_from_slice(&st.over_ticks.to_le_bytes());
+    match &st.learned {
+        Some(l) => {
+            out.push(1);
+            for rs in [&l.gate_shift, &l.entropy, &l.cos_dist] {
+                out.extend_from_slice(&rs.n.to_le_bytes());
+                out.extend_from_slice(&rs.mean.to_le_bytes());
+                out.extend_from_slice(&rs.var.to_le_bytes());
+            }
+        }
+        None => out.push(0),
     }
+    out.extend_from_slice(&st.hyst_ai_tell.to_le_bytes());
+    out.extend_from_slice(&(st.source_hyst.len() as u32).to_le_bytes());
+    for (source_id, h) in &st.source_hyst {
+        out.extend_from_slice(&(source_id.len() as u32).to_le_bytes());
+        out.extend_from_slice(source_id.as_bytes());
+        out.extend_from_slice(&h.rep.to_le_bytes());
+        out.extend_from_slice(&h.stall.to_le_bytes());
+        out.extend_from_slice(&h.ai_tell.to_le_bytes());
+    }
+    out
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn nsc_arbiter_restore(h: *mut NscArbiterSupervisor, bytes: *const u8, len: usize, merge: u8) -> i32 {
-    if h.is_null() || bytes.is_null() || len < 12 {
-        return -1;
+fn decode_state(bytes: &[u8], ver: u32) -> ArbiterState {
+    let mut r = Reader { data: bytes, i: 0 };
+    let mut st = ArbiterState::default();
+
+    if ver == 2 {
+        st.hyst_rep = r.u32().unwrap_or(0) as f32;
+        st.hyst_stall = r.u32().unwrap_or(0) as f32;
+    } else {
+        st.hyst_rep = r.f32().unwrap_or(0.0);
+        st.hyst_stall = r.f32().unwrap_or(0.0);
     }
-    let handle = &mut *h;
-    let data = std::slice::from_raw_parts(bytes, len);
+    st.tier = r.u32().unwrap_or(0);
+    st.tier_ticks = r.u32().unwrap_or(0);
+    st.last_ts = r.u64().filter(|&v| v != u64::MAX);
+    st.last_escalation_ts = r.u64().filter(|&v| v != u64::MAX);
+    st.ticks_since_escalation = r.u32().filter(|&v| v != u32::MAX);
+    st.over_ticks = r.u32().unwrap_or(0);
+    if r.u8() == Some(1) {
+        let mut stat = || -> Option<RunningStat> { Some(RunningStat { n: r.u64()?, mean: r.f32()?, var: r.f32()? }) };
+        if let (Some(gate_shift), Some(entropy), Some(cos_dist)) = (stat(), stat(), stat()) {
+            st.learned = Some(LearnedBaselines { gate_shift, entropy, cos_dist });
+        }
+    }
+    st.hyst_ai_tell = r.f32().unwrap_or(0.0);
+    for _ in 0..r.u32().unwrap_or(0) {
+        let mut counters = || -> Option<(String, SourceHyst)> {
+            let slen = r.u32()? as usize;
+            let source_id = std::str::from_utf8(r.take(slen)?).ok()?.to_string();
+            Some((source_id, SourceHyst { rep: r.f32()?, stall: r.f32()?, ai_tell: r.f32()? }))
+        };
+        let Some((source_id, h)) = counters() else { break };
+        st.source_hyst.insert(source_id, h);
+    }
+    st
+}
 
-    let mut i = 0usize;
-    let read_u32 = |data: &[u8], i: &mut usize| -> Option<u32> {
-        if *i + 4 > data.len() { return None; }
-        let v = u32::from_le_bytes(data[*i..*i+4].try_into().ok()?);
-        *i += 4;
-        Some(v)
-    };
+/// Little-endian cursor over snapshot bytes.
+struct Reader<'a> {
+    data: &'a [u8],
+    i: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.i + n > self.data.len() { return None; }
+        let out = &self.data[self.i..self.i + n];
+        self.i += n;
+        Some(out)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
 
-    let magic = match read_u32(data, &mut i) { Some(v) => v, None => return -2 };
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.take(8)?.try_into().ok()?))
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        Some(f32::from_le_bytes(self.take(4)?.try_into().ok()?))
+    }
+}
+
+/// Decode snapshot bytes. Errors are the negative rc values documented on `nsc_arbiter_restore`.
+fn decode_snapshot(data: &[u8]) -> Result<SupervisorSnapshot, i32> {
+    let mut r = Reader { data, i: 0 };
+
+    let magic = r.u32().ok_or(-2)?;
     if magic != SNAP_MAGIC {
-        return -8; // bad magic
+        return Err(-8); // bad magic
     }
-    let ver = match read_u32(data, &mut i) { Some(v) => v, None => return -2 };
-    if ver != SNAP_VERSION {
-        return -9; // unsupported version
+    let ver = r.u32().ok_or(-2)?;
+    if !(1..=SNAP_VERSION).contains(&ver) {
+        return Err(-9); // unsupported version
     }
 
-    let count = match read_u32(data, &mut i) { Some(v) => v as usize, None => return -2 };
+    let count = r.u32().ok_or(-2)? as usize;
+    let clock = if ver >= 3 { r.u64().ok_or(-2)? } else { 0 };
 
-    let mut states: Vec<(String, nsc_arbiter_core::ArbiterState)> = Vec::with_capacity(count);
+    let mut states: Vec<(String, ArbiterState)> = Vec::with_capacity(count);
 
     for _ in 0..count {
-        let slen = match read_u32(data, &mut i) { Some(v) => v as usize, None => return -3 };
-        if i + slen > data.len() { return -4; }
-        let id = match std::str::from_utf8(&data[i..i+slen]) {
+        let slen = r.u32().ok_or(-3)? as usize;
+        let id = match std::str::from_utf8(r.take(slen).ok_or(-4)?) {
             Ok(s) => s.to_string(),
-            Err(_) => return -5,
+            Err(_) => return Err(-5),
         };
-        i += slen;
-
-        let hyst_rep = match read_u32(data, &mut i) { Some(v) => v, None => return -6 };
-        let hyst_stall = match read_u32(data, &mut i) { Some(v) => v, None => return -7 };
 
-        let mut st = nsc_arbiter_core::ArbiterState::default();
-        st.hyst_rep = hyst_rep;
-        st.hyst_stall = hyst_stall;
+        let st = if ver == 1 {
+            let hyst_rep = r.u32().ok_or(-6)?;
+            let hyst_stall = r.u32().ok_or(-7)?;
+            ArbiterState { hyst_rep: hyst_rep as f32, hyst_stall: hyst_stall as f32, ..ArbiterState::default() }
+        } else {
+            let state_len = r.u32().ok_or(-6)? as usize;
+            decode_state(r.take(state_len).ok_or(-7)?, ver)
+        };
 
         states.push((id, st));
     }
 
-    let snap = SupervisorSnapshot { states };
-    if merge != 0 {
-        let _stats = handle.inner.restore_merge(snap);
-    } else {
-        let _stats = handle.inner.restore(snap);
+    let mut source_trust: Vec<(String, SourceTrust)> = Vec::new();
+    if let Some(n) = r.u32() {
+        for _ in 0..n {
+            let slen = r.u32().ok_or(-3)? as usize;
+            let id = match std::str::from_utf8(r.take(slen).ok_or(-4)?) {
+                Ok(s) => s.to_string(),
+                Err(_) => return Err(-5),
+            };
+            let reliability = r.f32().ok_or(-7)?;
+            let useful = r.u64().ok_or(-7)?;
+            let not_useful = r.u64().ok_or(-7)?;
+            source_trust.push((id, SourceTrust { reliability, useful, not_useful }));
+        }
     }
 
-    0
+    let mut text_memory: Vec<(String, String, TextMemory)> = Vec::new();
+    if let Some(n) = r.u32() {
+        for _ in 0..n {
+            let mut ids = [String::new(), String::new()];
+            for id in &mut ids {
+                let slen = r.u32().ok_or(-3)? as usize;
+                *id = match std::str::from_utf8(r.take(slen).ok_or(-4)?) {
+                    Ok(s) => s.to_string(),
+                    Err(_) => return Err(-5),
+                };
+            }
+            let mut memory = TextMemory::default();
+            for _ in 0..r.u32().ok_or(-7)? {
+                memory.signatures.push_back(r.u64().ok_or(-7)?);
+            }
+            let [id, source_id] = ids;
+            text_memory.push((id, source_id, memory));
+        }
+    }
+
+    // Cohorts are not exposed over the C ABI, so there are no pins to carry.
+    Ok(SupervisorSnapshot { states, clock, source_trust, cohorts: Vec::new(), text_memory })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_bytes_free(b: NscBytes) {
+    if !b.ptr.is_null() {
+        let slice_ptr = std::ptr::slice_from_raw_parts_mut(b.ptr, b.len);
+        drop(Box::from_raw(slice_ptr));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nsc_arbiter_restore(h: *mut NscArbiterSupervisor, bytes: *const u8, len: usize, merge: u8) -> i32 {
+    nsc_arbiter_restore_stats(h, bytes, len, merge).rc
 }
 
 #[no_mangle]
@@ -431,49 +1165,11 @@ pub unsafe extern "C" fn nsc_arbiter_restore_stats(
     let handle = &mut *h;
     let data = std::slice::from_raw_parts(bytes, len);
 
-    let mut i = 0usize;
-    let read_u32 = |data: &[u8], i: &mut usize| -> Option<u32> {
-        if *i + 4 > data.len() { return None; }
-        let v = u32::from_le_bytes(data[*i..*i+4].try_into().ok()?);
-        *i += 4;
-        Some(v)
+    let snap = match decode_snapshot(data) {
+        Ok(snap) => snap,
+        Err(rc) => return NscRestoreStats { applied: 0, overwritten: 0, rc },
     };
 
-    let magic = match read_u32(data, &mut i) { Some(v) => v, None => return NscRestoreStats { applied: 0, overwritten: 0, rc: -2 } };
-    if magic != SNAP_MAGIC {
-        return NscRestoreStats { applied: 0, overwritten: 0, rc: -8 };
-    }
-    let ver = match read_u32(data, &mut i) { Some(v) => v, None => return NscRestoreStats { applied: 0, overwritten: 0, rc: -2 } };
-    if ver != SNAP_VERSION {
-        return NscRestoreStats { applied: 0, overwritten: 0, rc: -9 };
-    }
-
-    let count = match read_u32(data, &mut i) { Some(v) => v as usize, None => return NscRestoreStats { applied: 0, overwritten: 0, rc: -2 } };
-
-    let mut states: Vec<(String, nsc_arbiter_core::ArbiterState)> = Vec::with_capacity(count);
-
-    for _ in 0..count {
-        let slen = match read_u32(data, &mut i) { Some(v) => v as usize, None => return NscRestoreStats { applied: 0, overwritten: 0, rc: -3 } };
-        if i + slen > data.len() {
-            return NscRestoreStats { applied: 0, overwritten: 0, rc: -4 };
-        }
-        let id = match std::str::from_utf8(&data[i..i+slen]) {
-            Ok(s) => s.to_string(),
-            Err(_) => return NscRestoreStats { applied: 0, overwritten: 0, rc: -5 },
-        };
-        i += slen;
-
-        let hyst_rep = match read_u32(data, &mut i) { Some(v) => v, None => return NscRestoreStats { applied: 0, overwritten: 0, rc: -6 } };
-        let hyst_stall = match read_u32(data, &mut i) { Some(v) => v, None => return NscRestoreStats { applied: 0, overwritten: 0, rc: -7 } };
-
-        let mut st = nsc_arbiter_core::ArbiterState::default();
-        st.hyst_rep = hyst_rep;
-        st.hyst_stall = hyst_stall;
-
-        states.push((id, st));
-    }
-
-    let snap = SupervisorSnapshot { states };
     let stats = if merge != 0 {
         handle.inner.restore_merge(snap)
     } else {
@@ -485,4 +1181,4 @@ pub unsafe extern "C" fn nsc_arbiter_restore_stats(
         overwritten: stats.overwritten as u32,
         rc: 0,
     }
-}
\ No newline at end of file
+}
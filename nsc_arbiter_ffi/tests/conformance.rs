@@ -0,0 +1,329 @@
+//! Conformance suite: the raw core API, the supervisor and the FFI must agree.
+//!
+//! Each case is a cfg and a sequence of single-event ticks for one intent. The core
+//! path builds the view and freeze flags by hand and calls `arbiter_idle_tick`; the
+//! supervisor and FFI paths ingest the equivalent events. All three must produce the
+//! case's expected escalation sequence. Multi-source cases send one event per source
+//! each tick, and the core path calls `arbiter_persona_tick_sources` instead.
+
+use std::ptr;
+
+use nsc_arbiter_core::{
+    arbiter_idle_tick, arbiter_persona_tick_sources, freeze_flags, AiTellAction, ArbiterCfg, ArbiterEvidenceView,
+    ArbiterState, Escalation, Evidence, PausePriority, PauseReason,
+};
+use nsc_arbiter_ffi::*;
+use nsc_arbiter_supervisor::{ArbiterSupervisor, BasicEvidenceBuilder, SignalEvent};
+
+const INTENT: &str = "intent:conf";
+/// Sets `rep_3p` only.
+const REP: &str = "abcabcabcabc";
+/// Sets `stall` only.
+const STALL: &str = "abababababababababab";
+/// Sets `ai_tell` only.
+const AI: &str = "As an AI, I cannot browse the web.";
+/// Sets no flag.
+const CLEAN: &str = "the quick brown fox";
+
+const N: Escalation = Escalation::None;
+const C: Escalation = Escalation::CritiquePass;
+const PR: Escalation = Escalation::Pause { reason: PauseReason::Repetition };
+const PS: Escalation = Escalation::Pause { reason: PauseReason::Stall };
+const PA: Escalation = Escalation::Pause { reason: PauseReason::AiTell };
+
+/// `(entropy, cosine, text)` of one tick.
+type Tick = (f32, f32, Option<&'static str>);
+
+/// `(entropy, cosine, [(source_id, text)])` of one multi-source tick.
+type SourcesTick = (f32, f32, &'static [(&'static str, &'static str)]);
+
+struct Case {
+    name: &'static str,
+    cfg: fn(&mut ArbiterCfg),
+    ticks: &'static [Tick],
+    expected: &'static [Escalation],
+}
+
+struct SourcesCase {
+    name: &'static str,
+    cfg: fn(&mut ArbiterCfg),
+    ticks: &'static [SourcesTick],
+    expected: &'static [Escalation],
+}
+
+const CASES: &[Case] = &[
+    Case {
+        name: "signals_without_flags",
+        cfg: |_| {},
+        ticks: &[(1.0, 0.9, None), (3.0, 0.9, None), (1.0, 0.5, None), (1.0, 0.9, Some(CLEAN))],
+        expected: &[N, C, C, N],
+    },
+    Case {
+        name: "rep_counts_once_per_tick",
+        cfg: |c| c.tau_rep = 2,
+        ticks: &[(3.0, 0.9, Some(REP)), (3.0, 0.9, Some(REP)), (3.0, 0.9, Some(REP)), (3.0, 0.9, Some(REP))],
+        expected: &[C, PR, C, PR],
+    },
+    Case {
+        name: "rep_persists_through_quiet_ticks",
+        cfg: |c| c.tau_rep = 2,
+        ticks: &[(1.0, 0.9, Some(REP)), (1.0, 0.9, Some(REP)), (1.0, 0.9, Some(REP))],
+        expected: &[N, PR, N],
+    },
+    Case {
+        name: "stall_persists_through_quiet_ticks",
+        cfg: |c| c.tau_stall = 2,
+        ticks: &[(1.0, 0.9, Some(STALL)), (1.0, 0.9, Some(STALL))],
+        expected: &[N, PS],
+    },
+    Case {
+        name: "clean_tick_resets_rep",
+        cfg: |c| c.tau_rep = 2,
+        ticks: &[(1.0, 0.9, Some(REP)), (1.0, 0.9, Some(CLEAN)), (1.0, 0.9, Some(REP))],
+        expected: &[N, N, N],
+    },
+    Case {
+        name: "stall_counts_once_per_tick",
+        cfg: |c| c.tau_stall = 3,
+        ticks: &[(3.0, 0.9, Some(STALL)), (3.0, 0.9, Some(STALL)), (3.0, 0.9, Some(STALL)), (3.0, 0.9, Some(STALL))],
+        expected: &[C, C, PS, C],
+    },
+    Case {
+        name: "hyst_disable_ignores_flags",
+        cfg: |c| c.hyst_disable = true,
+        ticks: &[(1.0, 0.9, Some(REP)), (3.0, 0.9, Some(STALL))],
+        expected: &[N, C],
+    },
+    Case {
+        name: "critique_first_defers_pause",
+        cfg: |c| c.pause_priority = PausePriority::CritiqueFirst,
+        ticks: &[(3.0, 0.9, Some(REP)), (1.0, 0.9, Some(REP))],
+        expected: &[C, PR],
+    },
+    Case {
+        name: "ai_tell_pauses_when_persistent",
+        cfg: |c| c.tau_ai_tell = 2,
+        ticks: &[(3.0, 0.9, Some(AI)), (3.0, 0.9, Some(AI)), (3.0, 0.9, Some(AI)), (1.0, 0.9, Some(AI))],
+        expected: &[C, PA, C, PA],
+    },
+    Case {
+        name: "ai_tell_escalates",
+        cfg: |c| {
+            c.tau_ai_tell = 2;
+            c.ai_tell_action = AiTellAction::Escalate;
+        },
+        // The second tick escalates on boilerplate alone and consumes the counter,
+        // which builds up again through the quiet third tick.
+        ticks: &[(3.0, 0.9, Some(AI)), (1.0, 0.9, Some(AI)), (1.0, 0.9, Some(AI)), (1.0, 0.9, Some(AI))],
+        expected: &[C, C, N, C],
+    },
+    Case {
+        name: "cooldown_holds_critique",
+        cfg: |c| c.cooldown_ticks = 2,
+        ticks: &[(3.0, 0.9, None), (3.0, 0.9, None), (3.0, 0.9, None), (3.0, 0.9, None)],
+        expected: &[C, N, N, C],
+    },
+];
+
+const SOURCES_CASES: &[SourcesCase] = &[
+    SourcesCase {
+        name: "sources_do_not_add_up",
+        cfg: |c| c.tau_stall = 2,
+        ticks: &[(3.0, 0.9, &[("stt", STALL), ("llm", CLEAN)]), (3.0, 0.9, &[("stt", CLEAN), ("llm", STALL)])],
+        expected: &[C, C],
+    },
+    SourcesCase {
+        name: "quiet_ticks_reset_each_source_by_its_own_flags",
+        cfg: |c| c.tau_stall = 2,
+        ticks: &[
+            (1.0, 1.0, &[("stt", STALL), ("llm", CLEAN)]),
+            (1.0, 1.0, &[("stt", CLEAN), ("llm", STALL)]),
+            (1.0, 1.0, &[("stt", STALL), ("llm", CLEAN)]),
+            (1.0, 1.0, &[("stt", STALL), ("llm", CLEAN)]),
+        ],
+        expected: &[N, N, N, PS],
+    },
+];
+
+/// One tick as the runners see it: every source shares the scalars.
+struct Batch {
+    entropy: f32,
+    cosine: f32,
+    sources: Vec<(&'static str, Option<&'static str>)>,
+}
+
+fn single(ticks: &[Tick]) -> Vec<Batch> {
+    ticks.iter().map(|&(entropy, cosine, text)| Batch { entropy, cosine, sources: vec![("llm", text)] }).collect()
+}
+
+fn per_source(ticks: &[SourcesTick]) -> Vec<Batch> {
+    ticks
+        .iter()
+        .map(|&(entropy, cosine, sources)| Batch {
+            entropy,
+            cosine,
+            sources: sources.iter().map(|&(source, text)| (source, Some(text))).collect(),
+        })
+        .collect()
+}
+
+fn run_core(cfg: &ArbiterCfg, ticks: &[Batch]) -> Vec<Escalation> {
+    let mut state = ArbiterState::default();
+    ticks
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let mut view = ArbiterEvidenceView::new(INTENT.to_string());
+            for &(source, _) in &b.sources {
+                view.push(Evidence {
+                    source_id: source.to_string(),
+                    intent_id: INTENT.to_string(),
+                    origin: "conf".to_string(),
+                    gate_shift: 0.0,
+                    avg_entropy: b.entropy,
+                    cosine_sim: b.cosine,
+                    rule_hits: 0,
+                    weight: 1.0,
+                    metrics: Vec::new(),
+                });
+            }
+            state.advance(i as u64 + 1, cfg);
+            match &b.sources[..] {
+                [(_, text)] => arbiter_idle_tick(&view, text.map(freeze_flags), cfg, &mut state),
+                sources => {
+                    let ff: Vec<_> = sources
+                        .iter()
+                        .filter_map(|&(source, text)| Some((source.to_string(), freeze_flags(text?))))
+                        .collect();
+                    arbiter_persona_tick_sources(&view, &ff, None, cfg, &mut state)
+                }
+            }
+        })
+        .collect()
+}
+
+fn run_supervisor(cfg: &ArbiterCfg, ticks: &[Batch]) -> Vec<Escalation> {
+    let builder = BasicEvidenceBuilder::default();
+    let sup = ArbiterSupervisor::new(1, cfg.clone());
+    ticks
+        .iter()
+        .map(|b| {
+            let events: Vec<SignalEvent<'_>> = b
+                .sources
+                .iter()
+                .map(|&(source, text)| {
+                    let mut ev = SignalEvent::new(INTENT, source, "conf")
+                        .with_scalar("entropy", b.entropy)
+                        .with_scalar("cosine", b.cosine);
+                    if let Some(t) = text {
+                        ev = ev.with_text(t);
+                    }
+                    ev
+                })
+                .collect();
+            sup.ingest(&builder, &events)[0].escalation
+        })
+        .collect()
+}
+
+fn s(s: &str) -> NscStr {
+    NscStr { ptr: s.as_ptr(), len: s.len() }
+}
+
+/// The FFI cfg for the fields the cases touch.
+fn nsc_cfg(cfg: &ArbiterCfg) -> NscCfg {
+    NscCfg {
+        tau_rep: cfg.tau_rep,
+        tau_stall: cfg.tau_stall,
+        hyst_disable: cfg.hyst_disable as u8,
+        pause_priority: (cfg.pause_priority == PausePriority::CritiqueFirst) as u8,
+        cooldown_ticks: cfg.cooldown_ticks,
+        tau_ai_tell: cfg.tau_ai_tell,
+        ai_tell_action: (cfg.ai_tell_action == AiTellAction::Escalate) as u8,
+        ..nsc_arbiter_cfg_default()
+    }
+}
+
+fn ffi_escalation(a: &NscAction) -> Escalation {
+    match (a.escalation, a.pause_reason) {
+        (NscEscalation::None, _) => Escalation::None,
+        (NscEscalation::CritiquePass, _) => Escalation::CritiquePass,
+        (NscEscalation::SecondLLM, _) => Escalation::SecondLLM,
+        (NscEscalation::Pause, NscPauseReason::Repetition) => PR,
+        (NscEscalation::Pause, NscPauseReason::Stall) => PS,
+        (NscEscalation::Pause, NscPauseReason::AiTell) => PA,
+        (NscEscalation::Pause, NscPauseReason::None) => panic!("pause without a reason"),
+    }
+}
+
+fn run_ffi(cfg: &ArbiterCfg, ticks: &[Batch]) -> Vec<Escalation> {
+    let h = nsc_arbiter_supervisor_new(1, nsc_cfg(cfg));
+    let out = ticks
+        .iter()
+        .map(|b| {
+            let kvs = [
+                NscScalarKV { key: s("entropy"), val: b.entropy },
+                NscScalarKV { key: s("cosine"), val: b.cosine },
+            ];
+            let events: Vec<NscEvent> = b
+                .sources
+                .iter()
+                .map(|&(source, text)| NscEvent {
+                    intent_id: s(INTENT),
+                    source_id: s(source),
+                    origin: s("conf"),
+                    text: text.map_or(NscStr { ptr: ptr::null(), len: 0 }, s),
+                    scalars_len: kvs.len(),
+                    scalars_ptr: kvs.as_ptr(),
+                    rule_hits: 0,
+                })
+                .collect();
+            let arr = unsafe { nsc_arbiter_ingest(h, events.as_ptr(), events.len()) };
+            assert_eq!(arr.actions_len, 1);
+            let esc = ffi_escalation(unsafe { &*arr.actions_ptr });
+            unsafe { nsc_arbiter_actions_free(arr) };
+            esc
+        })
+        .collect();
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    out
+}
+
+#[test]
+fn flag_texts_set_one_flag_each() {
+    let rep = freeze_flags(REP);
+    let stall = freeze_flags(STALL);
+    let ai = freeze_flags(AI);
+    assert!(rep.rep_3p && !rep.stall && !rep.ai_tell);
+    assert!(stall.stall && !stall.rep_3p && !stall.ai_tell);
+    assert!(ai.ai_tell && !ai.rep_3p && !ai.stall);
+    assert_eq!(freeze_flags(CLEAN), Default::default());
+}
+
+#[test]
+fn core_supervisor_and_ffi_agree() {
+    for case in CASES {
+        let mut cfg = ArbiterCfg::default();
+        (case.cfg)(&mut cfg);
+        assert_eq!(case.ticks.len(), case.expected.len(), "{}", case.name);
+
+        let ticks = single(case.ticks);
+        assert_eq!(run_core(&cfg, &ticks), case.expected, "core: {}", case.name);
+        assert_eq!(run_supervisor(&cfg, &ticks), case.expected, "supervisor: {}", case.name);
+        assert_eq!(run_ffi(&cfg, &ticks), case.expected, "ffi: {}", case.name);
+    }
+}
+
+#[test]
+fn multi_source_cases_agree() {
+    for case in SOURCES_CASES {
+        let mut cfg = ArbiterCfg::default();
+        (case.cfg)(&mut cfg);
+        assert_eq!(case.ticks.len(), case.expected.len(), "{}", case.name);
+
+        let ticks = per_source(case.ticks);
+        assert_eq!(run_core(&cfg, &ticks), case.expected, "core: {}", case.name);
+        assert_eq!(run_supervisor(&cfg, &ticks), case.expected, "supervisor: {}", case.name);
+        assert_eq!(run_ffi(&cfg, &ticks), case.expected, "ffi: {}", case.name);
+    }
+}
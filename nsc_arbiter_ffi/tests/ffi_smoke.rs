@@ -108,4 +108,513 @@ fn ffi_snapshot_restore_roundtrip() {
 
     unsafe { nsc_arbiter_bytes_free(snap) };
     unsafe { nsc_arbiter_supervisor_free(h) };
-}
\ No newline at end of file
+}
+
+#[test]
+fn ffi_second_llm_survives_snapshot() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    cfg.second_llm_min_ticks = 1;
+
+    let kv = NscScalarKV {
+        key: s("entropy"),
+        val: 5.0,
+    };
+    let ev = NscEvent {
+        intent_id: s("intent:ladder"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: 1,
+        scalars_ptr: &kv as *const NscScalarKV,
+        rule_hits: 0,
+    };
+
+    let ingest_one = |h: *mut NscArbiterSupervisor| -> NscEscalation {
+        let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+        assert_eq!(arr.actions_len, 1);
+        let esc = unsafe { (*arr.actions_ptr).escalation };
+        unsafe { nsc_arbiter_actions_free(arr) };
+        esc
+    };
+
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    assert_eq!(ingest_one(h), NscEscalation::CritiquePass);
+    assert_eq!(ingest_one(h), NscEscalation::SecondLLM);
+
+    // A fresh handle restored from the snapshot stays on the SecondLLM rung.
+    let snap = unsafe { nsc_arbiter_snapshot(h) };
+    let h2 = nsc_arbiter_supervisor_new(1, cfg);
+    let rc = unsafe { nsc_arbiter_restore(h2, snap.ptr as *const u8, snap.len, 0) };
+    assert_eq!(rc, 0);
+    assert_eq!(ingest_one(h2), NscEscalation::SecondLLM);
+
+    unsafe { nsc_arbiter_bytes_free(snap) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    unsafe { nsc_arbiter_supervisor_free(h2) };
+}
+
+#[test]
+fn ffi_repetitive_text_pauses() {
+    let cfg = nsc_arbiter_cfg_default();
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+
+    let ev = NscEvent {
+        intent_id: s("intent:loop"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: s("abcabcabcabcabcabcabcabc"),
+        scalars_len: 0,
+        scalars_ptr: ptr::null(),
+        rule_hits: 0,
+    };
+
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    assert_eq!(arr.actions_len, 1);
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.escalation, NscEscalation::Pause);
+    assert_eq!(a0.pause_reason, NscPauseReason::Repetition);
+    assert_eq!(a0.ff_rep_3p, 1);
+
+    unsafe { nsc_arbiter_actions_free(arr) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_trace_bitmask_and_margins() {
+    let cfg = nsc_arbiter_cfg_default();
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    unsafe { nsc_arbiter_set_trace(h, 1) };
+
+    let kvs = [
+        NscScalarKV { key: s("entropy"), val: 3.0 },
+        NscScalarKV { key: s("cosine"), val: 0.9 },
+    ];
+    let ev = NscEvent {
+        intent_id: s("intent:trace"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: kvs.len(),
+        scalars_ptr: kvs.as_ptr(),
+        rule_hits: 0,
+    };
+
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.has_trace, 1);
+    assert_eq!(a0.trace_fired, 1); // hi_entropy only
+    assert!((a0.trace_margins[0] - (3.0 - cfg.tau_e)).abs() < 1e-6);
+
+    unsafe { nsc_arbiter_actions_free(arr) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_cooldown_survives_snapshot() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    cfg.cooldown_time = 10;
+    cfg.decay_kind = 1;
+    cfg.decay_param = 4.0;
+
+    let kv = NscScalarKV {
+        key: s("entropy"),
+        val: 5.0,
+    };
+    let ev = NscEvent {
+        intent_id: s("intent:cool"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: 1,
+        scalars_ptr: &kv as *const NscScalarKV,
+        rule_hits: 0,
+    };
+
+    let ingest_at = |h: *mut NscArbiterSupervisor, now: u64| -> NscEscalation {
+        let arr = unsafe { nsc_arbiter_ingest_at(h, &ev as *const NscEvent, 1, now) };
+        let esc = unsafe { (*arr.actions_ptr).escalation };
+        unsafe { nsc_arbiter_actions_free(arr) };
+        esc
+    };
+
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    assert_eq!(ingest_at(h, 50), NscEscalation::CritiquePass);
+
+    let snap = unsafe { nsc_arbiter_snapshot(h) };
+    let h2 = nsc_arbiter_supervisor_new(1, cfg);
+    let stats = unsafe { nsc_arbiter_restore_stats(h2, snap.ptr as *const u8, snap.len, 0) };
+    assert_eq!(stats.rc, 0);
+    assert_eq!(stats.applied, 1);
+
+    assert_eq!(ingest_at(h2, 55), NscEscalation::None);
+    assert_eq!(ingest_at(h2, 60), NscEscalation::CritiquePass);
+
+    unsafe { nsc_arbiter_bytes_free(snap) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    unsafe { nsc_arbiter_supervisor_free(h2) };
+}
+
+#[test]
+fn ffi_reports_maturity_hold() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    cfg.min_dwell_ticks = 2;
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+
+    let kv = NscScalarKV {
+        key: s("entropy"),
+        val: 5.0,
+    };
+    let ev = NscEvent {
+        intent_id: s("intent:young"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: 1,
+        scalars_ptr: &kv as *const NscScalarKV,
+        rule_hits: 0,
+    };
+
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.escalation, NscEscalation::None);
+    assert_eq!(a0.held, NscHold::MinDwell);
+    unsafe { nsc_arbiter_actions_free(arr) };
+
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.escalation, NscEscalation::CritiquePass);
+    assert_eq!(a0.held, NscHold::None);
+    unsafe { nsc_arbiter_actions_free(arr) };
+
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_registered_metric_escalates() {
+    let cfg = nsc_arbiter_cfg_default();
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    assert_eq!(unsafe { nsc_arbiter_register_metric(ptr::null_mut(), s("latency"), 3, 0.0, 0, 1.0) }, -1);
+    // agg 3 = max, direction 0 = higher is worse
+    assert_eq!(unsafe { nsc_arbiter_register_metric(h, s("latency"), 3, 0.0, 0, 500.0) }, 0);
+    unsafe { nsc_arbiter_set_trace(h, 1) };
+
+    let kvs = [
+        NscScalarKV { key: s("cosine"), val: 1.0 },
+        NscScalarKV { key: s("latency"), val: 800.0 },
+    ];
+    let ev = NscEvent {
+        intent_id: s("intent:metric"),
+        source_id: s("probe"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: kvs.len(),
+        scalars_ptr: kvs.as_ptr(),
+        rule_hits: 0,
+    };
+
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.escalation, NscEscalation::CritiquePass);
+    assert_eq!(a0.trace_fired, 0);
+    assert_eq!(a0.metrics_len, 1);
+    let m = unsafe { &*a0.metrics_ptr };
+    let name = unsafe { std::slice::from_raw_parts(m.name.ptr, m.name.len) };
+    assert_eq!(name, b"latency");
+    assert_eq!(m.value, 800.0);
+    assert_eq!(m.fired, 1);
+
+    unsafe { nsc_arbiter_actions_free(arr) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_baselines_report_oddity() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    assert_eq!(cfg.tau_oddity, 1.0);
+    cfg.tau_oddity = 0.5;
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    unsafe { nsc_arbiter_set_trace(h, 1) };
+    let b = NscBaselines {
+        gate_shift_mu: 0.0,
+        gate_shift_sigma: 0.1,
+        entropy_mu: 1.0,
+        entropy_sigma: 0.1,
+        cos_dist_mu: 0.0,
+        cos_dist_sigma: 0.05,
+    };
+    assert_eq!(unsafe { nsc_arbiter_set_baselines(h, s("intent:odd"), b) }, 0);
+
+    let kvs = [
+        NscScalarKV { key: s("entropy"), val: 2.0 },
+        NscScalarKV { key: s("cosine"), val: 0.8 },
+    ];
+    let ev = NscEvent {
+        intent_id: s("intent:odd"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: kvs.len(),
+        scalars_ptr: kvs.as_ptr(),
+        rule_hits: 0,
+    };
+
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.escalation, NscEscalation::CritiquePass);
+    assert!(a0.oddity > 0.5);
+    assert_eq!(a0.trace_fired, 1 << 6); // oddity only
+    unsafe { nsc_arbiter_actions_free(arr) };
+
+    unsafe { nsc_arbiter_clear_baselines(h, s("intent:odd")) };
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!(a0.oddity, 0.0);
+    assert_eq!(a0.escalation, NscEscalation::None);
+    unsafe { nsc_arbiter_actions_free(arr) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_learned_baselines_survive_snapshot() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    cfg.tau_oddity = 0.5;
+    cfg.learn_kind = 1; // Welford
+    cfg.learn_warmup = 3;
+
+    let ingest_entropy = |h: *mut NscArbiterSupervisor, entropy: f32| -> (NscEscalation, f32) {
+        let kvs = [
+            NscScalarKV { key: s("entropy"), val: entropy },
+            NscScalarKV { key: s("cosine"), val: 0.95 },
+        ];
+        let ev = NscEvent {
+            intent_id: s("intent:learn"),
+            source_id: s("llm"),
+            origin: s("ffi"),
+            text: NscStr {
+                ptr: ptr::null(),
+                len: 0,
+            },
+            scalars_len: kvs.len(),
+            scalars_ptr: kvs.as_ptr(),
+            rule_hits: 0,
+        };
+        let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+        let a0 = unsafe { &*arr.actions_ptr };
+        let out = (a0.escalation, a0.oddity);
+        unsafe { nsc_arbiter_actions_free(arr) };
+        out
+    };
+
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    for x in [1.0, 1.1, 0.9] {
+        assert_eq!(ingest_entropy(h, x), (NscEscalation::None, 0.0));
+    }
+
+    let snap = unsafe { nsc_arbiter_snapshot(h) };
+    let h2 = nsc_arbiter_supervisor_new(1, cfg);
+    assert_eq!(unsafe { nsc_arbiter_restore(h2, snap.ptr as *const u8, snap.len, 0) }, 0);
+
+    let (esc, oddity) = ingest_entropy(h2, 2.0);
+    assert_eq!(esc, NscEscalation::CritiquePass);
+    assert!(oddity > 0.5);
+
+    unsafe { nsc_arbiter_bytes_free(snap) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    unsafe { nsc_arbiter_supervisor_free(h2) };
+}
+
+#[test]
+fn ffi_outcome_feedback_survives_snapshot() {
+    let h = nsc_arbiter_supervisor_new(1, nsc_arbiter_cfg_default());
+    assert_eq!(unsafe { nsc_arbiter_set_source_profile(h, s("vendor"), 0.6, 0.2, 0.8) }, 0);
+    unsafe { nsc_arbiter_set_trust_rate(h, 0.5) };
+
+    let kv = NscScalarKV { key: s("entropy"), val: 5.0 };
+    let ev = NscEvent {
+        intent_id: s("intent:trust"),
+        source_id: s("vendor"),
+        origin: s("ffi"),
+        text: NscStr {
+            ptr: ptr::null(),
+            len: 0,
+        },
+        scalars_len: 1,
+        scalars_ptr: &kv as *const NscScalarKV,
+        rule_hits: 0,
+    };
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    assert_eq!(unsafe { (*arr.actions_ptr).escalation }, NscEscalation::CritiquePass);
+    unsafe { nsc_arbiter_actions_free(arr) };
+
+    assert_eq!(unsafe { nsc_arbiter_report_outcome(h, s("intent:trust"), 1) }, 1);
+    assert_eq!(unsafe { nsc_arbiter_report_outcome(h, s("intent:trust"), 1) }, 0);
+
+    let snap = unsafe { nsc_arbiter_snapshot(h) };
+    let h2 = nsc_arbiter_supervisor_new(1, nsc_arbiter_cfg_default());
+    assert_eq!(unsafe { nsc_arbiter_restore(h2, snap.ptr as *const u8, snap.len, 0) }, 0);
+
+    let mut t = NscSourceTrust::default();
+    assert_eq!(unsafe { nsc_arbiter_source_trust(h2, s("vendor"), &mut t) }, 0);
+    assert!((t.reliability - 0.5).abs() < 1e-6);
+    assert_eq!((t.useful, t.not_useful), (1, 0));
+
+    unsafe { nsc_arbiter_bytes_free(snap) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    unsafe { nsc_arbiter_supervisor_free(h2) };
+}
+
+#[test]
+fn ffi_eviction_caps_intents() {
+    let h = nsc_arbiter_supervisor_new(1, nsc_arbiter_cfg_default());
+    unsafe { nsc_arbiter_set_eviction(h, 1, 0) };
+
+    for (i, id) in ["intent:a", "intent:b"].into_iter().enumerate() {
+        let kv = NscScalarKV { key: s("cosine"), val: 1.0 };
+        let ev = NscEvent {
+            intent_id: s(id),
+            source_id: s("llm"),
+            origin: s("ffi"),
+            text: NscStr {
+                ptr: ptr::null(),
+                len: 0,
+            },
+            scalars_len: 1,
+            scalars_ptr: &kv as *const NscScalarKV,
+            rule_hits: 0,
+        };
+        let arr = unsafe { nsc_arbiter_ingest_at(h, &ev as *const NscEvent, 1, i as u64 + 1) };
+        unsafe { nsc_arbiter_actions_free(arr) };
+    }
+
+    let stats = unsafe { nsc_arbiter_stats(h) };
+    assert_eq!((stats.intents, stats.evicted_capacity, stats.evicted_idle), (1, 1, 0));
+    assert_eq!(unsafe { nsc_arbiter_stats(ptr::null_mut()) }.intents, 0);
+
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_custom_ai_phrases_pause() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    cfg.tau_ai_tell = 1;
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    let phrases = [s("i'm just a bot")];
+    assert_eq!(unsafe { nsc_arbiter_set_ai_phrases(h, phrases.as_ptr(), phrases.len(), 1) }, 0);
+    assert_eq!(unsafe { nsc_arbiter_set_ai_phrases(ptr::null_mut(), phrases.as_ptr(), 1, 1) }, -1);
+
+    let ev = NscEvent {
+        intent_id: s("intent:bot"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: s("Sorry, I'm just a bot and cannot help."),
+        scalars_len: 0,
+        scalars_ptr: ptr::null(),
+        rule_hits: 0,
+    };
+    let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+    let a0 = unsafe { &*arr.actions_ptr };
+    assert_eq!((a0.escalation, a0.pause_reason), (NscEscalation::Pause, NscPauseReason::AiTell));
+    assert_eq!(a0.ff_ai_tell, 1);
+
+    unsafe { nsc_arbiter_actions_free(arr) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+}
+
+#[test]
+fn ffi_text_memory_survives_snapshot() {
+    let text = "Here is the same paragraph the generator keeps sending on every tick.";
+    let ev = NscEvent {
+        intent_id: s("intent:loop"),
+        source_id: s("llm"),
+        origin: s("ffi"),
+        text: s(text),
+        scalars_len: 0,
+        scalars_ptr: ptr::null(),
+        rule_hits: 0,
+    };
+    let repeat = |h: *mut NscArbiterSupervisor| {
+        let arr = unsafe { nsc_arbiter_ingest(h, &ev as *const NscEvent, 1) };
+        let flag = unsafe { (*arr.actions_ptr).ff_cross_tick_repeat };
+        unsafe { nsc_arbiter_actions_free(arr) };
+        flag
+    };
+
+    let h = nsc_arbiter_supervisor_new(1, nsc_arbiter_cfg_default());
+    assert_eq!(unsafe { nsc_arbiter_set_text_memory(ptr::null_mut(), 4, 10) }, -1);
+    assert_eq!(repeat(h), 0);
+    assert_eq!(unsafe { nsc_arbiter_set_text_memory(h, 4, 10) }, 0);
+    assert_eq!(repeat(h), 0);
+    assert_eq!(repeat(h), 1);
+
+    let bytes = unsafe { nsc_arbiter_snapshot(h) };
+    let h2 = nsc_arbiter_supervisor_new(1, nsc_arbiter_cfg_default());
+    assert_eq!(unsafe { nsc_arbiter_set_text_memory(h2, 4, 10) }, 0);
+    assert_eq!(unsafe { nsc_arbiter_restore(h2, bytes.ptr, bytes.len, 0) }, 0);
+    assert_eq!(repeat(h2), 1);
+
+    unsafe { nsc_arbiter_bytes_free(bytes) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    unsafe { nsc_arbiter_supervisor_free(h2) };
+}
+
+#[test]
+fn ffi_source_freeze_eligibility() {
+    let mut cfg = nsc_arbiter_cfg_default();
+    cfg.tau_stall = 2;
+    let ingest = |h: *mut NscArbiterSupervisor| {
+        let kvs = [NscScalarKV { key: s("entropy"), val: 3.0 }];
+        let ev = |source: &'static str, text: &'static str| NscEvent {
+            intent_id: s("intent:mix"),
+            source_id: s(source),
+            origin: s("ffi"),
+            text: s(text),
+            scalars_len: kvs.len(),
+            scalars_ptr: kvs.as_ptr(),
+            rule_hits: 0,
+        };
+        let evs = [ev("stt", "abababababababababab"), ev("llm", "the quick brown fox")];
+        let arr = unsafe { nsc_arbiter_ingest(h, evs.as_ptr(), evs.len()) };
+        let a0 = unsafe { &*arr.actions_ptr };
+        let out = (a0.escalation, a0.ff_stall);
+        unsafe { nsc_arbiter_actions_free(arr) };
+        out
+    };
+
+    let h = nsc_arbiter_supervisor_new(1, cfg);
+    assert_eq!(unsafe { nsc_arbiter_set_source_freeze_eligible(h, s("stt"), 0) }, -2);
+    assert_eq!(ingest(h), (NscEscalation::CritiquePass, 1));
+
+    // Per-source counters survive a snapshot round trip.
+    let bytes = unsafe { nsc_arbiter_snapshot(h) };
+    let h2 = nsc_arbiter_supervisor_new(1, cfg);
+    assert_eq!(unsafe { nsc_arbiter_restore(h2, bytes.ptr, bytes.len, 0) }, 0);
+    assert_eq!(ingest(h2), (NscEscalation::Pause, 1));
+
+    assert_eq!(unsafe { nsc_arbiter_set_source_profile(h, s("stt"), 1.0, 0.5, 1.0) }, 0);
+    assert_eq!(unsafe { nsc_arbiter_set_source_freeze_eligible(h, s("stt"), 0) }, 0);
+    assert_eq!(unsafe { nsc_arbiter_set_source_profile(h, s("stt"), 0.9, 0.5, 1.0) }, 0);
+    assert_eq!(ingest(h), (NscEscalation::CritiquePass, 0));
+    assert_eq!(ingest(h), (NscEscalation::CritiquePass, 0));
+
+    unsafe { nsc_arbiter_bytes_free(bytes) };
+    unsafe { nsc_arbiter_supervisor_free(h) };
+    unsafe { nsc_arbiter_supervisor_free(h2) };
+}
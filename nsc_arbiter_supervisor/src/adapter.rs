@@ -13,7 +13,7 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use nsc_arbiter_core::Evidence;
+use nsc_arbiter_core::{Evidence, MetricValue};
 
 /// A raw event from the outside world (queues, sensors, finance, etc.).
 ///
@@ -119,13 +119,7 @@ impl Normalizer {
         if !x.is_finite() {
             return 0.0;
         }
-        if x < -1.0 {
-            -1.0
-        } else if x > 1.0 {
-            1.0
-        } else {
-            x
-        }
+        x.clamp(-1.0, 1.0)
     }
 
     /// Normalize the standard arbiter scalars.
@@ -156,8 +150,10 @@ pub trait EvidenceBuilder {
 /// - "gate_shift"  -> `gate_shift`
 /// - "weight"      -> `weight`
 ///
-/// Missing scalars default to 0.0 (weight defaults to 1.0).
-#[derive(Clone, Debug)]
+/// Missing scalars default to 0.0 (weight defaults to 1.0). Every other finite scalar is
+/// passed through unnormalized as `Evidence::metrics`, sorted by key, for metrics declared
+/// in `ArbiterCfg::metrics`.
+#[derive(Clone, Debug, Default)]
 pub struct BasicEvidenceBuilder {
     pub normalizer: Normalizer,
     /// Optional scalar key overrides.
@@ -183,15 +179,6 @@ impl Default for BasicKeys {
     }
 }
 
-impl Default for BasicEvidenceBuilder {
-    fn default() -> Self {
-        Self {
-            normalizer: Normalizer::default(),
-            keys: BasicKeys::default(),
-        }
-    }
-}
-
 impl EvidenceBuilder for BasicEvidenceBuilder {
     fn build(&self, ev: &SignalEvent<'_>) -> Vec<Evidence> {
         let entropy = *ev.scalars.get(self.keys.entropy).unwrap_or(&0.0);
@@ -201,6 +188,15 @@ impl EvidenceBuilder for BasicEvidenceBuilder {
 
         let (entropy, cosine, gate_shift, weight) = self.normalizer.normalize(entropy, cosine, gate_shift, weight);
 
+        let standard = [self.keys.entropy, self.keys.cosine, self.keys.gate_shift, self.keys.weight];
+        let mut metrics: Vec<MetricValue> = ev
+            .scalars
+            .iter()
+            .filter(|(k, v)| v.is_finite() && !standard.contains(&k.as_ref()))
+            .map(|(k, &v)| MetricValue::new(k.as_ref(), v))
+            .collect();
+        metrics.sort_by(|a, b| a.name.cmp(&b.name));
+
         vec![Evidence {
             source_id: ev.source_id.to_string(),
             intent_id: ev.intent_id.to_string(),
@@ -210,6 +206,7 @@ impl EvidenceBuilder for BasicEvidenceBuilder {
             cosine_sim: cosine,
             rule_hits: ev.rule_hits,
             weight,
+            metrics,
         }]
     }
 }
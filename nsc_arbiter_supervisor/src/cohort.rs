@@ -0,0 +1,66 @@
+//! Deterministic cohort (A/B) assignment of cfg variants by `intent_id` hash.
+//!
+//! Each variant takes a fraction of the intents, in declaration order; the rest fall in
+//! `CONTROL_COHORT` and use the supervisor's base cfg. Assignment hashes the salt with
+//! the `intent_id`, so it is stable across runs and platforms, and changing the salt
+//! reshuffles every intent.
+
+use nsc_arbiter_core::ArbiterCfg;
+
+use crate::sharding::{fnv1a_u64, mix64};
+
+/// Cohort of intents not assigned to any variant.
+pub const CONTROL_COHORT: &str = "control";
+
+/// A named cfg variant and the share of intents it receives.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CohortVariant {
+    pub name: String,
+    /// In `0..=1`, clamped; variants past a cumulative total of 1 get nothing.
+    pub fraction: f32,
+    pub cfg: ArbiterCfg,
+}
+
+/// A salted set of cfg variants (see `ArbiterSupervisor::set_cohorts`).
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Cohorts {
+    pub salt: String,
+    pub variants: Vec<CohortVariant>,
+}
+
+impl Cohorts {
+    pub fn new(salt: impl Into<String>) -> Self {
+        Self { salt: salt.into(), variants: Vec::new() }
+    }
+
+    /// Add a variant; names should be unique and differ from `CONTROL_COHORT`.
+    pub fn with_variant(mut self, name: impl Into<String>, fraction: f32, cfg: ArbiterCfg) -> Self {
+        self.variants.push(CohortVariant { name: name.into(), fraction, cfg });
+        self
+    }
+
+    /// Cohort the hash places `intent_id` in.
+    pub fn assign(&self, intent_id: &str) -> &str {
+        let h = mix64(fnv1a_u64(&self.salt) ^ fnv1a_u64(intent_id));
+        // Top 53 bits as a uniform point in [0, 1).
+        let point = (h >> 11) as f64 / (1u64 << 53) as f64;
+        let mut upto = 0.0f64;
+        for v in &self.variants {
+            upto += v.fraction.clamp(0.0, 1.0) as f64;
+            if point < upto {
+                return &v.name;
+            }
+        }
+        CONTROL_COHORT
+    }
+
+    /// Cfg of the named variant; `None` for control and unknown names.
+    pub fn cfg(&self, cohort: &str) -> Option<&ArbiterCfg> {
+        self.variants.iter().find(|v| v.name == cohort).map(|v| &v.cfg)
+    }
+
+    /// Whether `cohort` is control or one of the variants.
+    pub fn contains(&self, cohort: &str) -> bool {
+        cohort == CONTROL_COHORT || self.variants.iter().any(|v| v.name == cohort)
+    }
+}
@@ -0,0 +1,569 @@
+//! Deterministic ingest journal and replay.
+//!
+//! With journaling enabled (`ArbiterSupervisor::enable_journal`), every ingest batch is
+//! recorded with its logical time, the supervisor's `cfg_fingerprint` and the resulting
+//! actions. `replay` re-runs a journal against another supervisor (fresh, or restored from
+//! the snapshot the journal starts at) and reports the first divergence.
+//!
+//! Events are recorded raw: the `SignalEvent` as ingested, owned and with scalars sorted
+//! by key, not the `Evidence` the builder made of it. Replay runs the builder again, so
+//! a changed builder or metric extraction shows up as a divergence instead of being
+//! skipped over; it also keeps text payloads, which freeze detection needs.
+//!
+//! No IO: `Journal::encode` / `Journal::decode` convert to and from compact bytes, and
+//! callers decide where they live.
+
+use std::borrow::Cow;
+
+use nsc_arbiter_core::{Escalation, FreezeFlags, Hold, MetricValue, PauseReason, Uncertainty};
+
+use crate::adapter::{EvidenceBuilder, SignalEvent};
+use crate::supervisor::{ActionEvent, ArbiterSupervisor};
+
+const JOURNAL_MAGIC: u32 = 0x4a43_534e; // "NSCJ" little-endian
+const JOURNAL_VERSION: u32 = 3;
+
+/// Owned copy of one `SignalEvent`, scalars sorted by key.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JournalEvent {
+    pub intent_id: String,
+    pub source_id: String,
+    pub origin: String,
+    pub text: Option<String>,
+    pub scalars: Vec<(String, f32)>,
+    pub rule_hits: u32,
+}
+
+impl JournalEvent {
+    pub fn from_signal(se: &SignalEvent<'_>) -> Self {
+        let mut scalars: Vec<(String, f32)> = se.scalars.iter().map(|(k, v)| (k.to_string(), *v)).collect();
+        scalars.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            intent_id: se.intent_id.to_string(),
+            source_id: se.source_id.to_string(),
+            origin: se.origin.to_string(),
+            text: se.text.as_ref().map(|t| t.to_string()),
+            scalars,
+            rule_hits: se.rule_hits,
+        }
+    }
+
+    /// Borrowing `SignalEvent` view, for re-ingesting.
+    pub fn to_signal(&self) -> SignalEvent<'_> {
+        let mut se = SignalEvent::new(self.intent_id.as_str(), self.source_id.as_str(), self.origin.as_str())
+            .with_rule_hits(self.rule_hits);
+        se.text = self.text.as_deref().map(Cow::Borrowed);
+        for (k, v) in &self.scalars {
+            se.scalars.insert(Cow::Borrowed(k.as_str()), *v);
+        }
+        se
+    }
+}
+
+/// The replay-relevant part of one `ActionEvent` (traces are not recorded).
+#[derive(Clone, Debug, PartialEq)]
+pub struct JournalAction {
+    pub intent_id: String,
+    pub escalation: Escalation,
+    pub held: Option<Hold>,
+    pub uncertainty: Option<Uncertainty>,
+    pub freeze_flags: Option<FreezeFlags>,
+    /// Flagged sources, sorted by `source_id` (see `ActionEvent::source_flags`).
+    pub source_flags: Vec<(String, FreezeFlags)>,
+    pub cohort: Option<String>,
+}
+
+impl From<&ActionEvent> for JournalAction {
+    fn from(a: &ActionEvent) -> Self {
+        Self {
+            intent_id: a.intent_id.clone(),
+            escalation: a.escalation,
+            held: a.held,
+            uncertainty: a.uncertainty.clone(),
+            freeze_flags: a.freeze_flags,
+            source_flags: a.source_flags.clone(),
+            cohort: a.cohort.clone(),
+        }
+    }
+}
+
+/// One recorded ingest batch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JournalEntry {
+    /// Logical time the batch was decided at.
+    pub now: u64,
+    /// `ArbiterSupervisor::cfg_fingerprint` when the batch was decided.
+    pub cfg_fingerprint: u64,
+    pub events: Vec<JournalEvent>,
+    /// Actions in output order (sorted by `intent_id`).
+    pub actions: Vec<JournalAction>,
+}
+
+/// Ordered ingest batches.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Journal {
+    pub entries: Vec<JournalEntry>,
+}
+
+/// Why journal bytes could not be decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JournalError {
+    Truncated,
+    BadMagic,
+    UnsupportedVersion(u32),
+    BadUtf8,
+    /// Unknown escalation / hold code.
+    BadCode(u8),
+}
+
+impl std::fmt::Display for JournalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JournalError::Truncated => write!(f, "journal truncated"),
+            JournalError::BadMagic => write!(f, "not a journal (bad magic)"),
+            JournalError::UnsupportedVersion(v) => write!(f, "unsupported journal version {v}"),
+            JournalError::BadUtf8 => write!(f, "journal string is not UTF-8"),
+            JournalError::BadCode(c) => write!(f, "unknown code {c} in journal"),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+impl Journal {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Compact little-endian binary encoding:
+    /// `[u32 magic][u32 version][u32 entry_count]`, then per entry
+    /// `[u64 now][u64 cfg_fingerprint][u32 n][event]*n [u32 n][action]*n`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.u32(JOURNAL_MAGIC);
+        w.u32(JOURNAL_VERSION);
+        w.u32(self.entries.len() as u32);
+        for e in &self.entries {
+            w.u64(e.now);
+            w.u64(e.cfg_fingerprint);
+            w.u32(e.events.len() as u32);
+            for ev in &e.events {
+                w.str(&ev.intent_id);
+                w.str(&ev.source_id);
+                w.str(&ev.origin);
+                w.opt_str(ev.text.as_deref());
+                w.u32(ev.rule_hits);
+                w.u32(ev.scalars.len() as u32);
+                for (k, v) in &ev.scalars {
+                    w.str(k);
+                    w.f32(*v);
+                }
+            }
+            w.u32(e.actions.len() as u32);
+            for a in &e.actions {
+                w.str(&a.intent_id);
+                w.u8(escalation_code(a.escalation));
+                w.u8(hold_code(a.held));
+                match &a.uncertainty {
+                    Some(u) => {
+                        w.u8(1);
+                        w.f32(u.avg_entropy);
+                        w.f32(u.cosine_sim);
+                        w.u32(u.rule_hits);
+                        w.f32(u.gate_shift);
+                        w.f32(u.total_weight);
+                        w.u32(u.source_count);
+                        w.f32(u.oddity);
+                        w.u32(u.metrics.len() as u32);
+                        for m in &u.metrics {
+                            w.str(&m.name);
+                            w.f32(m.value);
+                        }
+                    }
+                    None => w.u8(0),
+                }
+                w.u8(a.freeze_flags.map_or(0, flag_bits));
+                w.u32(a.source_flags.len() as u32);
+                for (source_id, ff) in &a.source_flags {
+                    w.str(source_id);
+                    w.u8(flag_bits(*ff));
+                }
+                w.opt_str(a.cohort.as_deref());
+            }
+        }
+        w.0
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, JournalError> {
+        let mut r = Reader { data, i: 0 };
+        if r.u32()? != JOURNAL_MAGIC {
+            return Err(JournalError::BadMagic);
+        }
+        let ver = r.u32()?;
+        if ver != JOURNAL_VERSION {
+            return Err(JournalError::UnsupportedVersion(ver));
+        }
+
+        let mut journal = Journal::default();
+        for _ in 0..r.u32()? {
+            let mut entry = JournalEntry { now: r.u64()?, cfg_fingerprint: r.u64()?, ..JournalEntry::default() };
+            for _ in 0..r.u32()? {
+                let mut ev = JournalEvent {
+                    intent_id: r.str()?,
+                    source_id: r.str()?,
+                    origin: r.str()?,
+                    text: r.opt_str()?,
+                    rule_hits: r.u32()?,
+                    scalars: Vec::new(),
+                };
+                for _ in 0..r.u32()? {
+                    ev.scalars.push((r.str()?, r.f32()?));
+                }
+                entry.events.push(ev);
+            }
+            for _ in 0..r.u32()? {
+                let intent_id = r.str()?;
+                let escalation = escalation_from_code(r.u8()?)?;
+                let held = hold_from_code(r.u8()?)?;
+                let uncertainty = match r.u8()? {
+                    0 => None,
+                    _ => {
+                        let mut u = Uncertainty {
+                            avg_entropy: r.f32()?,
+                            cosine_sim: r.f32()?,
+                            rule_hits: r.u32()?,
+                            gate_shift: r.f32()?,
+                            total_weight: r.f32()?,
+                            source_count: r.u32()?,
+                            oddity: r.f32()?,
+                            metrics: Vec::new(),
+                        };
+                        for _ in 0..r.u32()? {
+                            u.metrics.push(MetricValue::new(r.str()?, r.f32()?));
+                        }
+                        Some(u)
+                    }
+                };
+                let freeze_flags = flags_from_bits(r.u8()?);
+                let mut source_flags = Vec::new();
+                for _ in 0..r.u32()? {
+                    let source_id = r.str()?;
+                    source_flags.push((source_id, flags_from_bits(r.u8()?).unwrap_or_default()));
+                }
+                let cohort = r.opt_str()?;
+                entry.actions.push(JournalAction {
+                    intent_id,
+                    escalation,
+                    held,
+                    uncertainty,
+                    freeze_flags,
+                    source_flags,
+                    cohort,
+                });
+            }
+            journal.entries.push(entry);
+        }
+        Ok(journal)
+    }
+}
+
+/// What differed at the first divergence.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DivergenceKind {
+    /// The replaying supervisor's configuration differs from the recorded one.
+    CfgMismatch { recorded: u64, actual: u64 },
+    /// Recorded action not produced on replay.
+    MissingAction,
+    /// Action produced on replay but not recorded.
+    UnexpectedAction,
+    Escalation { recorded: Escalation, actual: Escalation },
+    Held { recorded: Option<Hold>, actual: Option<Hold> },
+    /// Telemetry field differs (bit-for-bit). Registry metrics are named `metric:<name>`.
+    Telemetry { field: String, recorded: Option<f32>, actual: Option<f32> },
+    FreezeFlags { recorded: Option<FreezeFlags>, actual: Option<FreezeFlags> },
+    /// Per-source flags differ; `source_id` is the first source that differs.
+    SourceFlags { source_id: String, recorded: Option<FreezeFlags>, actual: Option<FreezeFlags> },
+    Cohort { recorded: Option<String>, actual: Option<String> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divergence {
+    /// Index of the journal entry.
+    pub entry: usize,
+    /// `None` for batch-level divergences (`CfgMismatch`).
+    pub intent_id: Option<String>,
+    pub kind: DivergenceKind,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Entries re-ingested (including the diverging one).
+    pub entries_replayed: usize,
+    pub divergence: Option<Divergence>,
+}
+
+impl ReplayReport {
+    /// True if every entry reproduced its recorded actions.
+    pub fn is_faithful(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+/// Re-ingest every journal entry into `sup` at its recorded logical time and compare
+/// the actions. Stops at the first divergence. `builder` must match the one used when
+/// recording.
+pub fn replay<B: EvidenceBuilder>(sup: &ArbiterSupervisor, builder: &B, journal: &Journal) -> ReplayReport {
+    let mut report = ReplayReport::default();
+    for (i, entry) in journal.entries.iter().enumerate() {
+        let actual = sup.cfg_fingerprint();
+        if actual != entry.cfg_fingerprint {
+            report.divergence = Some(Divergence {
+                entry: i,
+                intent_id: None,
+                kind: DivergenceKind::CfgMismatch { recorded: entry.cfg_fingerprint, actual },
+            });
+            return report;
+        }
+
+        let events: Vec<SignalEvent<'_>> = entry.events.iter().map(JournalEvent::to_signal).collect();
+        let out = sup.ingest_at(builder, &events, entry.now);
+        report.entries_replayed += 1;
+
+        let actual: Vec<JournalAction> = out.iter().map(JournalAction::from).collect();
+        if let Some((intent_id, kind)) = first_difference(&entry.actions, &actual) {
+            report.divergence = Some(Divergence { entry: i, intent_id: Some(intent_id), kind });
+            return report;
+        }
+    }
+    report
+}
+
+fn first_difference(recorded: &[JournalAction], actual: &[JournalAction]) -> Option<(String, DivergenceKind)> {
+    // Both sides are sorted by intent_id.
+    let (mut i, mut j) = (0, 0);
+    while i < recorded.len() || j < actual.len() {
+        let (r, a) = match (recorded.get(i), actual.get(j)) {
+            (Some(r), Some(a)) if r.intent_id == a.intent_id => (r, a),
+            (Some(r), a) if a.is_none_or(|a| r.intent_id < a.intent_id) => {
+                return Some((r.intent_id.clone(), DivergenceKind::MissingAction))
+            }
+            (_, Some(a)) => return Some((a.intent_id.clone(), DivergenceKind::UnexpectedAction)),
+            _ => unreachable!(),
+        };
+        if let Some(kind) = action_difference(r, a) {
+            return Some((r.intent_id.clone(), kind));
+        }
+        i += 1;
+        j += 1;
+    }
+    None
+}
+
+fn action_difference(r: &JournalAction, a: &JournalAction) -> Option<DivergenceKind> {
+    if r.escalation != a.escalation {
+        return Some(DivergenceKind::Escalation { recorded: r.escalation, actual: a.escalation });
+    }
+    if r.held != a.held {
+        return Some(DivergenceKind::Held { recorded: r.held, actual: a.held });
+    }
+    let fields = |u: Option<&Uncertainty>| -> Vec<(String, f32)> {
+        let Some(u) = u else { return Vec::new() };
+        let mut f = vec![
+            ("avg_entropy".to_string(), u.avg_entropy),
+            ("cosine_sim".to_string(), u.cosine_sim),
+            ("rule_hits".to_string(), u.rule_hits as f32),
+            ("gate_shift".to_string(), u.gate_shift),
+            ("total_weight".to_string(), u.total_weight),
+            ("source_count".to_string(), u.source_count as f32),
+            ("oddity".to_string(), u.oddity),
+        ];
+        f.extend(u.metrics.iter().map(|m| (format!("metric:{}", m.name), m.value)));
+        f
+    };
+    let (rf, af) = (fields(r.uncertainty.as_ref()), fields(a.uncertainty.as_ref()));
+    for k in 0..rf.len().max(af.len()) {
+        let (rv, av) = (rf.get(k), af.get(k));
+        let same = match (rv, av) {
+            (Some(x), Some(y)) => x.0 == y.0 && x.1.to_bits() == y.1.to_bits(),
+            _ => false,
+        };
+        if !same {
+            let field = rv.or(av).map(|f| f.0.clone()).unwrap_or_default();
+            return Some(DivergenceKind::Telemetry {
+                field,
+                recorded: rv.map(|f| f.1),
+                actual: av.map(|f| f.1),
+            });
+        }
+    }
+    if r.freeze_flags != a.freeze_flags {
+        return Some(DivergenceKind::FreezeFlags { recorded: r.freeze_flags, actual: a.freeze_flags });
+    }
+    if r.source_flags != a.source_flags {
+        let source_id = r
+            .source_flags
+            .iter()
+            .chain(&a.source_flags)
+            .map(|(s, _)| s)
+            .filter(|s| flags_of(&r.source_flags, s) != flags_of(&a.source_flags, s))
+            .min()
+            .expect("differing source flags")
+            .clone();
+        return Some(DivergenceKind::SourceFlags {
+            recorded: flags_of(&r.source_flags, &source_id),
+            actual: flags_of(&a.source_flags, &source_id),
+            source_id,
+        });
+    }
+    if r.cohort != a.cohort {
+        return Some(DivergenceKind::Cohort { recorded: r.cohort.clone(), actual: a.cohort.clone() });
+    }
+    None
+}
+
+fn flags_of(flags: &[(String, FreezeFlags)], source_id: &str) -> Option<FreezeFlags> {
+    flags.iter().find(|(s, _)| s == source_id).map(|(_, ff)| *ff)
+}
+
+/// Bit 0 marks presence; then `rep_3p`, `stall`, `ai_tell`, `cross_tick_repeat`.
+fn flag_bits(ff: FreezeFlags) -> u8 {
+    1 | (ff.rep_3p as u8) << 1 | (ff.stall as u8) << 2 | (ff.ai_tell as u8) << 3 | (ff.cross_tick_repeat as u8) << 4
+}
+
+fn flags_from_bits(bits: u8) -> Option<FreezeFlags> {
+    (bits & 1 != 0).then_some(FreezeFlags {
+        rep_3p: bits & 2 != 0,
+        stall: bits & 4 != 0,
+        ai_tell: bits & 8 != 0,
+        cross_tick_repeat: bits & 16 != 0,
+    })
+}
+
+fn escalation_code(e: Escalation) -> u8 {
+    match e {
+        Escalation::None => 0,
+        Escalation::CritiquePass => 1,
+        Escalation::SecondLLM => 2,
+        Escalation::Pause { reason: PauseReason::Repetition } => 3,
+        Escalation::Pause { reason: PauseReason::Stall } => 4,
+        Escalation::Pause { reason: PauseReason::AiTell } => 5,
+    }
+}
+
+fn escalation_from_code(c: u8) -> Result<Escalation, JournalError> {
+    Ok(match c {
+        0 => Escalation::None,
+        1 => Escalation::CritiquePass,
+        2 => Escalation::SecondLLM,
+        3 => Escalation::Pause { reason: PauseReason::Repetition },
+        4 => Escalation::Pause { reason: PauseReason::Stall },
+        5 => Escalation::Pause { reason: PauseReason::AiTell },
+        _ => return Err(JournalError::BadCode(c)),
+    })
+}
+
+fn hold_code(h: Option<Hold>) -> u8 {
+    match h {
+        None => 0,
+        Some(Hold::Cooldown) => 1,
+        Some(Hold::MinEvidenceWeight) => 2,
+        Some(Hold::MinSources) => 3,
+        Some(Hold::MinDwell) => 4,
+    }
+}
+
+fn hold_from_code(c: u8) -> Result<Option<Hold>, JournalError> {
+    Ok(match c {
+        0 => None,
+        1 => Some(Hold::Cooldown),
+        2 => Some(Hold::MinEvidenceWeight),
+        3 => Some(Hold::MinSources),
+        4 => Some(Hold::MinDwell),
+        _ => return Err(JournalError::BadCode(c)),
+    })
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, v: u8) {
+        self.0.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.0.extend_from_slice(s.as_bytes());
+    }
+
+    fn opt_str(&mut self, s: Option<&str>) {
+        match s {
+            Some(s) => {
+                self.u8(1);
+                self.str(s);
+            }
+            None => self.u8(0),
+        }
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    i: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], JournalError> {
+        if self.data.len() - self.i < n {
+            return Err(JournalError::Truncated);
+        }
+        let out = &self.data[self.i..self.i + n];
+        self.i += n;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, JournalError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, JournalError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("4 bytes")))
+    }
+
+    fn u64(&mut self) -> Result<u64, JournalError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("8 bytes")))
+    }
+
+    fn f32(&mut self) -> Result<f32, JournalError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().expect("4 bytes")))
+    }
+
+    fn str(&mut self) -> Result<String, JournalError> {
+        let n = self.u32()? as usize;
+        let bytes = self.take(n)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| JournalError::BadUtf8)
+    }
+
+    fn opt_str(&mut self) -> Result<Option<String>, JournalError> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => self.str().map(Some),
+        }
+    }
+}
@@ -7,6 +7,10 @@
 //! - shard state by `intent_id` (deterministic)
 //! - convert domain signals into `Evidence` via adapters
 //! - invoke arbiter core decision logic
+//! - optionally journal ingest batches for deterministic replay
+//! - optionally assign cfg variants to cohorts of intents by hash
+//! - optionally shadow-evaluate a candidate cfg against live decisions
+//! - optionally remember recent text payloads per intent and source to flag cross-tick repeats
 //!
 //! Non-goals:
 //! - no IO
@@ -14,6 +18,10 @@
 //! - no policy logic (lives in core)
 
 pub mod adapter;
+pub mod cohort;
+pub mod journal;
+pub mod shadow;
+pub mod sharding;
 pub mod supervisor;
 
 pub use adapter::{
@@ -25,7 +33,48 @@ pub use adapter::{
     build_evidence_batch,
 };
 
+pub use cohort::{
+    Cohorts,
+    CohortVariant,
+    CONTROL_COHORT,
+};
+
+pub use journal::{
+    replay,
+    Divergence,
+    DivergenceKind,
+    Journal,
+    JournalAction,
+    JournalEntry,
+    JournalError,
+    JournalEvent,
+    ReplayReport,
+};
+
+pub use shadow::{
+    MAX_SHADOW_REPORTS,
+    ShadowDiff,
+    ShadowStats,
+    ShadowTick,
+};
+
+pub use sharding::{
+    ShardStrategy,
+    FnvModulo,
+    JumpHash,
+    Rendezvous,
+    strategy_by_name,
+};
+
 pub use supervisor::{
     ArbiterSupervisor,
     ActionEvent,
+    CheckpointToken,
+    EvictionCfg,
+    EvictionHook,
+    SnapshotError,
+    SupervisorDelta,
+    SupervisorSnapshotV2,
+    SupervisorStats,
+    SNAPSHOT_SCHEMA_VERSION,
 };
@@ -0,0 +1,133 @@
+//! Shadow evaluation of a candidate `ArbiterCfg`.
+//!
+//! With a shadow set (`ArbiterSupervisor::set_shadow_cfg` / `set_shadow`), every ingest
+//! batch is also decided against the candidate cfg and its own per-intent states. Shadow
+//! decisions are never returned as actions; instead each batch with a disagreement yields
+//! a `ShadowTick` listing the intents where live and shadow disagree, with both traces,
+//! and `ShadowStats` keeps running agreement counters over every batch. Undrained reports
+//! are bounded by `MAX_SHADOW_REPORTS`. Shadows are not part of snapshots.
+
+use std::collections::{HashMap, VecDeque};
+
+use nsc_arbiter_core::{
+    arbiter_persona_tick_sources_traced, ArbiterCfg, ArbiterState, DecisionTrace, Escalation, PersonaBaselines,
+};
+
+use crate::supervisor::{ActionEvent, ShardWork};
+
+/// Undrained `ShadowTick`s kept at most; the oldest go first. `ShadowStats` still
+/// counts every batch.
+pub const MAX_SHADOW_REPORTS: usize = 1024;
+
+/// One intent on which live and shadow escalated differently.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShadowDiff {
+    pub intent_id: String,
+    pub live: DecisionTrace,
+    pub shadow: DecisionTrace,
+}
+
+/// Shadow report for one ingest batch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ShadowTick {
+    /// Logical time the batch was decided at.
+    pub now: u64,
+    /// Intents decided by both live and shadow.
+    pub decisions: usize,
+    /// Disagreements, sorted by `intent_id`.
+    pub diffs: Vec<ShadowDiff>,
+}
+
+/// Running agreement counters since the shadow was set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ShadowStats {
+    pub ticks: u64,
+    pub decisions: u64,
+    /// Same escalation (including both `None`).
+    pub agreed: u64,
+    /// Live escalated, shadow did not.
+    pub live_only: u64,
+    /// Shadow escalated, live did not.
+    pub shadow_only: u64,
+    /// Both escalated, at different tiers.
+    pub tier_differs: u64,
+}
+
+impl ShadowStats {
+    pub fn disagreed(&self) -> u64 {
+        self.live_only + self.shadow_only + self.tier_differs
+    }
+
+    /// Fraction of decisions that agreed; `1.0` before any decision.
+    pub fn agreement_rate(&self) -> f64 {
+        if self.decisions == 0 {
+            1.0
+        } else {
+            self.agreed as f64 / self.decisions as f64
+        }
+    }
+}
+
+/// Candidate cfg, its states and the undrained reports.
+#[derive(Debug)]
+pub(crate) struct Shadow {
+    pub(crate) cfg: ArbiterCfg,
+    pub(crate) states: HashMap<String, ArbiterState>,
+    pub(crate) reports: VecDeque<ShadowTick>,
+    pub(crate) stats: ShadowStats,
+}
+
+impl Shadow {
+    pub(crate) fn new(cfg: ArbiterCfg, states: HashMap<String, ArbiterState>) -> Self {
+        Self { cfg, states, reports: VecDeque::new(), stats: ShadowStats::default() }
+    }
+
+    /// Decide `work` with the candidate cfg and compare against `live`.
+    ///
+    /// Both are sorted by `intent_id` and hold one entry per intent; `live` actions
+    /// carry traces. Per-intent overrides and registered baselines apply as for live.
+    pub(crate) fn tick(
+        &mut self,
+        now: u64,
+        work: Vec<ShardWork>,
+        live: &[ActionEvent],
+        overrides: &HashMap<String, ArbiterCfg>,
+        baselines: &HashMap<String, PersonaBaselines>,
+    ) {
+        let mut report = ShadowTick { now, decisions: work.len(), diffs: Vec::new() };
+        for ((intent_id, view, ff), action) in work.into_iter().zip(live) {
+            debug_assert_eq!(intent_id, action.intent_id);
+            let cfg = overrides.get(&intent_id).unwrap_or(&self.cfg);
+            let state = self.states.entry(intent_id.clone()).or_default();
+            state.advance(now, cfg);
+
+            let learned = state.learned_baselines(cfg);
+            let b = baselines.get(&intent_id).or(learned.as_ref());
+            let trace = arbiter_persona_tick_sources_traced(&view, &ff, b, cfg, state);
+            state.learn(&view, cfg);
+
+            let counter = match (action.escalation, trace.escalation) {
+                (l, s) if l == s => &mut self.stats.agreed,
+                (_, Escalation::None) => &mut self.stats.live_only,
+                (Escalation::None, _) => &mut self.stats.shadow_only,
+                _ => &mut self.stats.tier_differs,
+            };
+            *counter += 1;
+            if action.escalation != trace.escalation {
+                report.diffs.push(ShadowDiff {
+                    intent_id,
+                    live: action.trace.clone().expect("live traces are on while shadowing"),
+                    shadow: trace,
+                });
+            }
+        }
+        self.stats.ticks += 1;
+        self.stats.decisions += report.decisions as u64;
+        if !report.diffs.is_empty() {
+            if self.reports.len() == MAX_SHADOW_REPORTS {
+                self.reports.pop_front();
+            }
+            self.reports.push_back(report);
+        }
+    }
+}
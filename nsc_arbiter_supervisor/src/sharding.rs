@@ -0,0 +1,121 @@
+//! Shard placement strategies: which shard owns an `intent_id`.
+//!
+//! All strategies are deterministic and stable across runs and platforms, so a
+//! snapshot restored into a supervisor with the same strategy and shard count lands
+//! every intent on the same shard.
+
+/// Maps an `intent_id` to a shard index in `0..shard_count`.
+///
+/// Implementations must be pure: the same inputs always give the same shard.
+/// `shard_count` is at least 1.
+pub trait ShardStrategy: std::fmt::Debug + Send + Sync {
+    fn shard_for(&self, intent_id: &str, shard_count: usize) -> usize;
+
+    /// Stable name recorded in full snapshots (see `strategy_by_name`). Custom strategies
+    /// keep the default `None` and must be supplied again on restore.
+    fn name(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// Look up a built-in strategy by its `ShardStrategy::name`.
+pub fn strategy_by_name(name: &str) -> Option<Box<dyn ShardStrategy>> {
+    match name {
+        "fnv_modulo" => Some(Box::new(FnvModulo)),
+        "jump_hash" => Some(Box::new(JumpHash)),
+        "rendezvous" => Some(Box::new(Rendezvous)),
+        _ => None,
+    }
+}
+
+/// Deterministic FNV-1a hash (stable across runs).
+pub fn fnv1a_u64(s: &str) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+    for b in s.as_bytes() {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    h
+}
+
+/// `fnv1a(intent_id) % shard_count`. The historical default; resharding moves most intents.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FnvModulo;
+
+impl ShardStrategy for FnvModulo {
+    fn shard_for(&self, intent_id: &str, shard_count: usize) -> usize {
+        if shard_count <= 1 {
+            return 0;
+        }
+        (fnv1a_u64(intent_id) as usize) % shard_count
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("fnv_modulo")
+    }
+}
+
+/// Jump consistent hash (Lamping & Veach). Growing from `n` to `n + 1` shards moves
+/// only about `1 / (n + 1)` of the intents, all of them onto the new shard.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JumpHash;
+
+impl ShardStrategy for JumpHash {
+    fn shard_for(&self, intent_id: &str, shard_count: usize) -> usize {
+        if shard_count <= 1 {
+            return 0;
+        }
+        let mut key = fnv1a_u64(intent_id);
+        let mut b: i64 = -1;
+        let mut j: i64 = 0;
+        while j < shard_count as i64 {
+            b = j;
+            key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+            j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+        }
+        b as usize
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("jump_hash")
+    }
+}
+
+/// Rendezvous (highest random weight) hashing: every shard scores the intent and the
+/// highest score wins. Adding or removing a shard only moves the intents it wins or
+/// held. Costs `O(shard_count)` per lookup.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rendezvous;
+
+impl ShardStrategy for Rendezvous {
+    fn shard_for(&self, intent_id: &str, shard_count: usize) -> usize {
+        if shard_count <= 1 {
+            return 0;
+        }
+        let h = fnv1a_u64(intent_id);
+        let mut best = 0;
+        let mut best_score = 0;
+        for shard in 0..shard_count {
+            let score = mix64(h ^ mix64(shard as u64));
+            // Strict `>` keeps the lowest index on (vanishingly rare) ties.
+            if shard == 0 || score > best_score {
+                best = shard;
+                best_score = score;
+            }
+        }
+        best
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("rendezvous")
+    }
+}
+
+/// SplitMix64 finalizer.
+#[inline]
+pub(crate) fn mix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
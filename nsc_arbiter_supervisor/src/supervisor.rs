@@ -6,36 +6,185 @@
 //! - applies optional `SourceProfiles`
 //! - runs the core decision functions
 //!
-//! No IO. No async. Concurrency is achieved by sharding state by `intent_id`.
+//! No IO. No async. Concurrency is achieved by sharding state by `intent_id`;
+//! `ingest_parallel` additionally decides shards on scoped worker threads.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use nsc_arbiter_core::{
-    apply_source_profiles, arbiter_idle_tick, freeze_flags, ArbiterCfg, ArbiterEvidenceView,
-    ArbiterState, Escalation, FreezeFlags, SourceProfiles,
+    apply_source_profiles_with_trust, arbiter_persona_tick_sources, arbiter_persona_tick_sources_traced,
+    freeze_flags_cfg, union_flags, ArbiterCfg, ArbiterEvidenceView, ArbiterState, DecisionTrace, Escalation, FreezeFlags,
+    Hold, PersonaBaselines, SourceProfile, SourceProfiles, SourceTrust, SourceTrusts, TextMemory,
 };
 
 use crate::adapter::{build_evidence_batch, EvidenceBuilder, SignalEvent};
+use crate::cohort::Cohorts;
+use crate::journal::{Journal, JournalAction, JournalEntry, JournalEvent};
+use crate::shadow::{Shadow, ShadowStats, ShadowTick};
+use crate::sharding::{fnv1a_u64, strategy_by_name, FnvModulo, ShardStrategy};
 
 /// Output action from the supervisor.
 #[derive(Clone, Debug)]
 pub struct ActionEvent {
     pub intent_id: String,
     pub escalation: Escalation,
+    /// Set when an escalation was warranted but held back (maturity gate, cool-down).
+    pub held: Option<Hold>,
     /// Optional telemetry; useful for logging/monitoring without re-aggregating.
+    /// `oddity` is only non-zero for intents with registered persona baselines.
     pub uncertainty: Option<nsc_arbiter_core::Uncertainty>,
-    /// Optional freeze flags derived from text payloads.
+    /// Optional freeze flags derived from text payloads, OR-ed over sources.
     pub freeze_flags: Option<FreezeFlags>,
+    /// Sources whose text raised a freeze flag, with their flags, sorted by `source_id`.
+    pub source_flags: Vec<(String, FreezeFlags)>,
+    /// Per-predicate explanation; only populated when tracing is enabled.
+    pub trace: Option<DecisionTrace>,
+    /// Cohort the intent was decided in; `None` unless cohorts are set.
+    pub cohort: Option<String>,
 }
 
 /// Snapshot of supervisor state for storage-agnostic persistence.
 ///
 /// This is intentionally pure data: callers decide how/where to store it.
 //#[derive(Clone, Debug, Default)]
-#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct SupervisorSnapshot {
     /// Per-intent arbiter state.
     pub states: Vec<(String, ArbiterState)>,
+    /// Supervisor logical clock at snapshot time.
+    #[serde(default)]
+    pub clock: u64,
+    /// Learned per-source reliability, sorted by `source_id`.
+    #[serde(default)]
+    pub source_trust: Vec<(String, SourceTrust)>,
+    /// Cohort each intent is pinned to (see `set_cohorts`), sorted by `intent_id`.
+    #[serde(default)]
+    pub cohorts: Vec<(String, String)>,
+    /// Recent payload signatures per `(intent_id, source_id)` (see `FreezeCfg::recent_texts`), sorted.
+    #[serde(default)]
+    pub text_memory: Vec<(String, String, TextMemory)>,
+}
+
+/// Schema version written by `ArbiterSupervisor::snapshot_full`.
+///
+/// Version 1 is the state-only `SupervisorSnapshot`; upgrade it with
+/// `SupervisorSnapshotV2::from_v1`.
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 2;
+
+/// Full-fidelity snapshot: every piece of configuration plus all state, enough for
+/// `ArbiterSupervisor::restore_full` to rebuild a supervisor that decides identically.
+///
+/// Not captured: the eviction hook (a closure), the eviction counters, the shadow cfg
+/// with its states, reports and stats, and the ingest journal (drain it with
+/// `take_journal` first).
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SupervisorSnapshotV2 {
+    /// Always `SNAPSHOT_SCHEMA_VERSION` when written by this crate.
+    pub schema_version: u32,
+    /// `nsc_arbiter_supervisor` version that wrote the snapshot (informational).
+    #[serde(default)]
+    pub crate_version: String,
+    pub cfg: ArbiterCfg,
+    /// Per-intent cfg overrides, sorted by `intent_id`.
+    #[serde(default)]
+    pub cfg_overrides: Vec<(String, ArbiterCfg)>,
+    /// Source profiles sorted by `source_id`; `None` when profiles are off.
+    #[serde(default)]
+    pub source_profiles: Option<Vec<(String, SourceProfile)>>,
+    /// Registered persona baselines, sorted by `intent_id`.
+    #[serde(default)]
+    pub baselines: Vec<(String, PersonaBaselines)>,
+    pub trust_rate: f32,
+    #[serde(default)]
+    pub trace: bool,
+    pub shards: usize,
+    /// `ShardStrategy::name`; `None` for custom strategies.
+    #[serde(default)]
+    pub shard_strategy: Option<String>,
+    #[serde(default)]
+    pub eviction: EvictionCfg,
+    #[serde(default)]
+    pub cohorts: Option<Cohorts>,
+    /// Per-intent state, clock and source trust.
+    pub state: SupervisorSnapshot,
+}
+
+impl SupervisorSnapshotV2 {
+    /// Migrate a version-1 state snapshot. Version 1 carried no configuration, so it is
+    /// taken from `cfg` with a single `FnvModulo` shard and otherwise default settings.
+    pub fn from_v1(snap: SupervisorSnapshot, cfg: ArbiterCfg) -> Self {
+        Self {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            crate_version: String::new(),
+            cfg,
+            cfg_overrides: Vec::new(),
+            source_profiles: None,
+            baselines: Vec::new(),
+            trust_rate: 0.1,
+            trace: false,
+            shards: 1,
+            shard_strategy: FnvModulo.name().map(str::to_string),
+            eviction: EvictionCfg::default(),
+            cohorts: None,
+            state: snap,
+        }
+    }
+}
+
+/// Why a full snapshot could not be restored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// Written by a newer (or unknown) schema.
+    UnsupportedVersion(u32),
+    /// The named shard strategy is not built in; use `restore_full_with_strategy`.
+    UnknownShardStrategy(String),
+    /// Taken with a custom shard strategy; use `restore_full_with_strategy`.
+    CustomShardStrategy,
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::UnsupportedVersion(v) => write!(f, "unsupported snapshot schema version {v}"),
+            SnapshotError::UnknownShardStrategy(name) => write!(f, "unknown shard strategy {name:?}"),
+            SnapshotError::CustomShardStrategy => write!(f, "snapshot was taken with a custom shard strategy"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Position in a supervisor's change history (see `ArbiterSupervisor::snapshot_delta`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CheckpointToken(pub u64);
+
+impl CheckpointToken {
+    /// Before any change: a delta since `ORIGIN` carries every intent.
+    pub const ORIGIN: CheckpointToken = CheckpointToken(0);
+}
+
+/// Intents changed or removed between two checkpoint tokens.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SupervisorDelta {
+    /// Token the delta was taken against.
+    pub since: CheckpointToken,
+    /// Pass to the next `snapshot_delta`.
+    pub token: CheckpointToken,
+    /// Current state of every intent changed since `since`, sorted by `intent_id`.
+    pub changed: Vec<(String, ArbiterState)>,
+    /// Intents removed (cleared or evicted) since `since`, sorted.
+    pub removed: Vec<String>,
+    /// Supervisor logical clock at delta time.
+    pub clock: u64,
+    /// Full learned source trust (small), sorted by `source_id`.
+    pub source_trust: Vec<(String, SourceTrust)>,
+    /// Pinned cohorts of the changed intents, sorted by `intent_id`.
+    #[serde(default)]
+    pub cohorts: Vec<(String, String)>,
+    /// Text memory of the changed intents, sorted by `(intent_id, source_id)`.
+    #[serde(default)]
+    pub text_memory: Vec<(String, String, TextMemory)>,
 }
 
 /// Simple observability counters returned by restore/import operations.
@@ -47,26 +196,127 @@ pub struct RestoreStats {
     pub overwritten: usize,
 }
 
+/// One intent's pending decision: id, grouped evidence, freeze flags per source (sorted).
+pub(crate) type ShardWork = (String, ArbiterEvidenceView, Vec<(String, FreezeFlags)>);
+
+/// Observability counters for a supervisor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SupervisorStats {
+    /// Intents currently holding state.
+    pub intents: usize,
+    /// Intents evicted because their shard was over `max_intents_per_shard`.
+    pub evicted_capacity: u64,
+    /// Intents evicted after `idle_ttl` without a tick.
+    pub evicted_idle: u64,
+}
+
+/// Bounds on per-intent state. `0` disables each limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EvictionCfg {
+    /// Keep at most this many intents per shard, evicting least recently ticked first.
+    pub max_intents_per_shard: usize,
+    /// Evict intents not ticked for more than this many logical time units.
+    pub idle_ttl: u64,
+}
+
+/// Receives every evicted `(intent_id, ArbiterState)`, e.g. to spill it to storage.
+pub type EvictionHook = Box<dyn Fn(String, ArbiterState) + Send + Sync>;
+
 #[derive(Default, Debug)]
 struct Shard {
     states: HashMap<String, ArbiterState>,
+    /// Recency index: `(last_ts, intent_id)` for every entry of `states`.
+    lru: BTreeSet<(u64, String)>,
+    /// Sources behind each intent's latest unreported escalation.
+    escalated_sources: HashMap<String, Vec<String>>,
+    /// Generation of each intent's latest change, removals included (see `snapshot_delta`).
+    changed_at: HashMap<String, u64>,
+    /// Change index: `(generation, intent_id)` for every entry of `changed_at`.
+    changes: BTreeSet<(u64, String)>,
+    /// Cohort each intent was first decided in; kept while the intent has state.
+    cohorts: HashMap<String, String>,
+    /// Recent payload signatures per intent and source; kept while the intent has state.
+    texts: HashMap<String, BTreeMap<String, TextMemory>>,
 }
 
-/// Deterministic FNV-1a hash (stable across runs).
-fn fnv1a_u64(s: &str) -> u64 {
-    let mut h: u64 = 0xcbf29ce484222325;
-    for b in s.as_bytes() {
-        h ^= *b as u64;
-        h = h.wrapping_mul(0x100000001b3);
+impl Shard {
+    fn recency(state: &ArbiterState) -> u64 {
+        state.last_ts.unwrap_or(0)
+    }
+
+    /// Record a change to `intent_id` at generation `gen`.
+    fn touch(&mut self, intent_id: &str, gen: u64) {
+        if let Some(old) = self.changed_at.insert(intent_id.to_string(), gen) {
+            self.changes.remove(&(old, intent_id.to_string()));
+        }
+        self.changes.insert((gen, intent_id.to_string()));
     }
-    h
-}
 
-fn shard_index(intent_id: &str, shard_count: usize) -> usize {
-    if shard_count <= 1 {
-        return 0;
+    /// Insert without recording a change (used when moving state between shards).
+    fn place(&mut self, intent_id: String, state: ArbiterState) -> Option<ArbiterState> {
+        if let Some(old) = self.states.get(&intent_id) {
+            self.lru.remove(&(Self::recency(old), intent_id.clone()));
+        }
+        self.lru.insert((Self::recency(&state), intent_id.clone()));
+        self.states.insert(intent_id, state)
+    }
+
+    fn insert(&mut self, intent_id: String, state: ArbiterState, gen: u64) -> Option<ArbiterState> {
+        self.touch(&intent_id, gen);
+        self.place(intent_id, state)
+    }
+
+    fn remove(&mut self, intent_id: &str, gen: u64) -> Option<ArbiterState> {
+        self.escalated_sources.remove(intent_id);
+        self.cohorts.remove(intent_id);
+        self.texts.remove(intent_id);
+        let state = self.states.remove(intent_id)?;
+        self.lru.remove(&(Self::recency(&state), intent_id.to_string()));
+        self.touch(intent_id, gen);
+        Some(state)
+    }
+
+    fn clear(&mut self, gen: u64) {
+        let ids: Vec<String> = self.states.keys().cloned().collect();
+        for id in ids {
+            self.touch(&id, gen);
+        }
+        self.states.clear();
+        self.lru.clear();
+        self.escalated_sources.clear();
+        self.cohorts.clear();
+        self.texts.clear();
+    }
+
+    /// Evict idle intents, then least recent ones over capacity, into `out`.
+    fn evict(&mut self, limits: EvictionCfg, now: u64, gen: u64, out: &mut Vec<(String, ArbiterState, bool)>) {
+        if limits.idle_ttl > 0 {
+            while let Some((ts, _)) = self.lru.first() {
+                if now.saturating_sub(*ts) <= limits.idle_ttl {
+                    break;
+                }
+                let (_, id) = self.lru.pop_first().expect("lru entry");
+                if let Some(state) = self.remove(&id, gen) {
+                    out.push((id, state, false));
+                }
+            }
+        }
+        if limits.max_intents_per_shard > 0 {
+            while self.states.len() > limits.max_intents_per_shard {
+                let (_, id) = self.lru.pop_first().expect("lru covers states");
+                if let Some(state) = self.remove(&id, gen) {
+                    out.push((id, state, true));
+                }
+            }
+        }
     }
-    (fnv1a_u64(intent_id) as usize) % shard_count
+}
+
+/// Map entries sorted by key, for deterministic export.
+fn sorted_entries<V: Clone>(map: &HashMap<String, V>) -> Vec<(String, V)> {
+    let mut out: Vec<(String, V)> = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
 }
 
 /// A sharded supervisor. One "arbiter instance" is one `(intent_id -> ArbiterState)` entry.
@@ -74,22 +324,80 @@ fn shard_index(intent_id: &str, shard_count: usize) -> usize {
 /// - `shards == 1` is the default and behaves like a single-threaded supervisor.
 /// - Increasing `shards` improves throughput by reducing contention (when you later add
 ///   threaded execution), while keeping state isolated per shard.
-#[derive(Debug)]
 pub struct ArbiterSupervisor {
     cfg: ArbiterCfg,
     /// Optional per-intent cfg overrides.
     cfg_overrides: HashMap<String, ArbiterCfg>,
     profiles: Option<SourceProfiles>,
+    /// Learned source reliability; moves profile weights inside their bands.
+    trust: std::sync::Mutex<SourceTrusts>,
+    /// Step size of each `report_outcome` update.
+    trust_rate: f32,
+    /// Per-intent persona baselines for oddity scoring.
+    baselines: HashMap<String, PersonaBaselines>,
+    /// Attach a `DecisionTrace` to every `ActionEvent`.
+    trace: bool,
     shards: usize,
-    // NOTE: State is behind a Mutex for interior mutability. This crate does not spawn threads.
-    // If a caller wants to share the supervisor across threads, they can wrap the whole
-    // `ArbiterSupervisor` in an `Arc` externally.
+    /// Places intents on shards (`FnvModulo` unless chosen at construction).
+    strategy: Box<dyn ShardStrategy>,
+    /// Logical clock: advanced by one per `ingest`, or set by `ingest_at`.
+    clock: AtomicU64,
+    /// Current change generation; `snapshot_delta` hands it out as a token and advances it.
+    generation: AtomicU64,
+    eviction: EvictionCfg,
+    on_evict: Option<EvictionHook>,
+    /// Ingest journal; `None` unless enabled.
+    journal: Option<std::sync::Mutex<Journal>>,
+    /// Salted cfg variants picked by `intent_id` hash; `None` unless set.
+    cohorts: Option<Cohorts>,
+    /// Candidate cfg decided alongside the live one; `None` unless set.
+    shadow: Option<std::sync::Mutex<Shadow>>,
+    /// Cached `cfg_fingerprint`; every setter of a fingerprinted field clears it.
+    fingerprint: std::sync::OnceLock<u64>,
+    evicted_capacity: AtomicU64,
+    evicted_idle: AtomicU64,
+    // NOTE: State is behind a Mutex for interior mutability. Only `ingest_parallel` spawns
+    // (scoped) threads; everything else runs on the caller's thread. If a caller wants to
+    // share the supervisor across threads, they can wrap the whole `ArbiterSupervisor` in
+    // an `Arc` externally.
     state_shards: Vec<std::sync::Mutex<Shard>>,
 }
 
+impl std::fmt::Debug for ArbiterSupervisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArbiterSupervisor")
+            .field("cfg", &self.cfg)
+            .field("cfg_overrides", &self.cfg_overrides)
+            .field("profiles", &self.profiles)
+            .field("trust", &self.trust)
+            .field("trust_rate", &self.trust_rate)
+            .field("baselines", &self.baselines)
+            .field("trace", &self.trace)
+            .field("shards", &self.shards)
+            .field("strategy", &self.strategy)
+            .field("clock", &self.clock)
+            .field("generation", &self.generation)
+            .field("eviction", &self.eviction)
+            .field("on_evict", &self.on_evict.as_ref().map(|_| "Fn"))
+            .field("journal", &self.journal)
+            .field("cohorts", &self.cohorts)
+            .field("shadow", &self.shadow)
+            .field("fingerprint", &self.fingerprint)
+            .field("state_shards", &self.state_shards)
+            .finish()
+    }
+}
+
 impl ArbiterSupervisor {
     /// Create a supervisor with `shards` (concurrency count). `shards=1` is the default.
     pub fn new(shards: usize, cfg: ArbiterCfg) -> Self {
+        Self::with_shard_strategy(shards, cfg, FnvModulo)
+    }
+
+    /// Create a supervisor placing intents on shards with `strategy`.
+    ///
+    /// Prefer `JumpHash` or `Rendezvous` if you plan to `reshard` a live supervisor.
+    pub fn with_shard_strategy<S: ShardStrategy + 'static>(shards: usize, cfg: ArbiterCfg, strategy: S) -> Self {
         let shards = shards.max(1);
         let mut state_shards = Vec::with_capacity(shards);
         for _ in 0..shards {
@@ -100,28 +408,126 @@ impl ArbiterSupervisor {
             cfg,
             cfg_overrides: HashMap::new(),
             profiles: None,
+            trust: std::sync::Mutex::new(SourceTrusts::new()),
+            trust_rate: 0.1,
+            baselines: HashMap::new(),
+            trace: false,
             shards,
+            strategy: Box::new(strategy),
+            clock: AtomicU64::new(0),
+            generation: AtomicU64::new(1),
+            eviction: EvictionCfg::default(),
+            on_evict: None,
+            journal: None,
+            cohorts: None,
+            shadow: None,
+            fingerprint: std::sync::OnceLock::new(),
+            evicted_capacity: AtomicU64::new(0),
+            evicted_idle: AtomicU64::new(0),
             state_shards,
         }
     }
 
     /// Set source profiles. These weight evidence by `source_id`.
     pub fn set_source_profiles(&mut self, profiles: SourceProfiles) {
+        self.fingerprint.take();
         self.profiles = Some(profiles);
     }
 
+    /// Set (or replace) the profile of one source.
+    pub fn set_source_profile(&mut self, source_id: impl Into<String>, profile: SourceProfile) {
+        self.fingerprint.take();
+        self.profiles.get_or_insert_with(SourceProfiles::new).insert(source_id.into(), profile);
+    }
+
+    /// Profile of one source, if source profiles are set and it has one.
+    pub fn source_profile(&self, source_id: &str) -> Option<&SourceProfile> {
+        self.profiles.as_ref()?.get(source_id)
+    }
+
     /// Clear source profiles.
     pub fn clear_source_profiles(&mut self) {
+        self.fingerprint.take();
         self.profiles = None;
     }
 
+    /// Set how far one `report_outcome` moves a source's reliability (0..=1, default 0.1).
+    pub fn set_trust_rate(&mut self, rate: f32) {
+        self.fingerprint.take();
+        self.trust_rate = rate;
+    }
+
+    /// Report whether the latest escalation for `intent_id` turned out to be useful.
+    ///
+    /// Every source that carried weight on that tick moves its reliability towards +1
+    /// (useful) or -1. Reliability shifts the source's profile weight inside its
+    /// `[min_weight, max_weight]` band, so it only matters while source profiles are set.
+    /// Each escalation can be reported once; returns the number of sources updated.
+    pub fn report_outcome(&self, intent_id: &str, useful: bool) -> usize {
+        let sources = match self.state_for_mut(intent_id).escalated_sources.remove(intent_id) {
+            Some(s) => s,
+            None => return 0,
+        };
+        let mut trust = self.trust.lock().expect("arbiter supervisor trust mutex poisoned");
+        for source in &sources {
+            trust.entry(source.clone()).or_default().record(useful, self.trust_rate);
+        }
+        sources.len()
+    }
+
+    /// Learned reliability of every source with feedback, sorted by `source_id`.
+    pub fn source_trust(&self) -> Vec<(String, SourceTrust)> {
+        let trust = self.trust.lock().expect("arbiter supervisor trust mutex poisoned");
+        let mut out: Vec<(String, SourceTrust)> = trust.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        out
+    }
+
+    /// Register persona baselines for `intent_id`; its ticks then carry an oddity score
+    /// compared against `ArbiterCfg::tau_oddity`. Registered baselines take precedence
+    /// over ones learned via `ArbiterCfg::baseline_learning`.
+    pub fn set_baselines(&mut self, intent_id: impl Into<String>, baselines: PersonaBaselines) {
+        self.fingerprint.take();
+        self.baselines.insert(intent_id.into(), baselines);
+    }
+
+    /// Remove persona baselines for `intent_id`.
+    pub fn clear_baselines(&mut self, intent_id: &str) {
+        self.fingerprint.take();
+        self.baselines.remove(intent_id);
+    }
+
+    /// Persona baselines registered for `intent_id`, if any.
+    pub fn baselines(&self, intent_id: &str) -> Option<&PersonaBaselines> {
+        self.baselines.get(intent_id)
+    }
+
+    /// Enable or disable decision traces on `ActionEvent` (off by default).
+    pub fn set_trace(&mut self, enabled: bool) {
+        self.trace = enabled;
+    }
+
+    /// Default cfg (used for intents without an override).
+    pub fn cfg(&self) -> &ArbiterCfg {
+        &self.cfg
+    }
+
+    /// Mutable default cfg, e.g. to register metrics after construction.
+    /// Per-intent overrides are not touched.
+    pub fn cfg_mut(&mut self) -> &mut ArbiterCfg {
+        self.fingerprint.take();
+        &mut self.cfg
+    }
+
     /// Override cfg for a specific `intent_id`.
     pub fn set_cfg_override(&mut self, intent_id: impl Into<String>, cfg: ArbiterCfg) {
+        self.fingerprint.take();
         self.cfg_overrides.insert(intent_id.into(), cfg);
     }
 
     /// Remove cfg override for a specific `intent_id`.
     pub fn clear_cfg_override(&mut self, intent_id: &str) {
+        self.fingerprint.take();
         self.cfg_overrides.remove(intent_id);
     }
 
@@ -157,7 +563,9 @@ impl ArbiterSupervisor {
         }
 
         out.sort_by(|a, b| a.0.cmp(&b.0));
-        SupervisorSnapshot { states: out }
+        let cohorts = self.pinned_cohorts(out.iter().map(|(id, _)| id));
+        let text_memory = self.text_memories(out.iter().map(|(id, _)| id));
+        SupervisorSnapshot { states: out, clock: self.clock(), source_trust: self.source_trust(), cohorts, text_memory }
     }
 
     /// Export a snapshot containing only the provided `intent_id`s.
@@ -182,7 +590,12 @@ impl ArbiterSupervisor {
     /// This overwrites any existing per-intent state currently held by the supervisor.
     /// No IO, no policy: callers decide how the snapshot is stored.
     pub fn restore(&self, snap: SupervisorSnapshot) -> RestoreStats {
-        self.import_state(snap.states)
+        self.clock.store(snap.clock, Ordering::Relaxed);
+        *self.trust.lock().expect("arbiter supervisor trust mutex poisoned") = snap.source_trust.into_iter().collect();
+        let stats = self.import_state(snap.states);
+        self.pin_cohorts(snap.cohorts);
+        self.put_text_memories(snap.text_memory);
+        stats
     }
 
     /// Restore supervisor state by merging a snapshot into the current state.
@@ -193,7 +606,148 @@ impl ArbiterSupervisor {
     /// This is useful when you want best-effort recovery but also want to keep any
     /// progress accumulated in-memory since the last successful save.
     pub fn restore_merge(&self, snap: SupervisorSnapshot) -> RestoreStats {
-        self.import_state_merge(snap.states)
+        self.clock.fetch_max(snap.clock, Ordering::Relaxed);
+        self.trust
+            .lock()
+            .expect("arbiter supervisor trust mutex poisoned")
+            .extend(snap.source_trust);
+        let stats = self.import_state_merge(snap.states);
+        self.pin_cohorts(snap.cohorts);
+        self.put_text_memories(snap.text_memory);
+        stats
+    }
+
+    /// Export configuration and state as a versioned full-fidelity snapshot.
+    ///
+    /// Deterministic: every list is sorted by id.
+    pub fn snapshot_full(&self) -> SupervisorSnapshotV2 {
+        SupervisorSnapshotV2 {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            cfg: self.cfg.clone(),
+            cfg_overrides: sorted_entries(&self.cfg_overrides),
+            source_profiles: self.profiles.as_ref().map(sorted_entries),
+            baselines: sorted_entries(&self.baselines),
+            trust_rate: self.trust_rate,
+            trace: self.trace,
+            shards: self.shards,
+            shard_strategy: self.strategy.name().map(str::to_string),
+            eviction: self.eviction,
+            cohorts: self.cohorts.clone(),
+            state: self.export_state(),
+        }
+    }
+
+    /// Rebuild a supervisor from `snapshot_full` output.
+    ///
+    /// Fails for schema versions other than `SNAPSHOT_SCHEMA_VERSION`, and for snapshots
+    /// taken with a custom shard strategy (see `restore_full_with_strategy`).
+    pub fn restore_full(snap: SupervisorSnapshotV2) -> Result<Self, SnapshotError> {
+        let strategy = match &snap.shard_strategy {
+            Some(name) => strategy_by_name(name).ok_or_else(|| SnapshotError::UnknownShardStrategy(name.clone()))?,
+            None => return Err(SnapshotError::CustomShardStrategy),
+        };
+        Self::restore_full_boxed(snap, strategy)
+    }
+
+    /// Like `restore_full`, placing intents with `strategy` instead of the recorded one.
+    pub fn restore_full_with_strategy<S: ShardStrategy + 'static>(
+        snap: SupervisorSnapshotV2,
+        strategy: S,
+    ) -> Result<Self, SnapshotError> {
+        Self::restore_full_boxed(snap, Box::new(strategy))
+    }
+
+    fn restore_full_boxed(snap: SupervisorSnapshotV2, strategy: Box<dyn ShardStrategy>) -> Result<Self, SnapshotError> {
+        if snap.schema_version != SNAPSHOT_SCHEMA_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snap.schema_version));
+        }
+        let mut sup = Self::with_shard_strategy(snap.shards, snap.cfg, FnvModulo);
+        sup.strategy = strategy;
+        sup.cfg_overrides = snap.cfg_overrides.into_iter().collect();
+        sup.profiles = snap.source_profiles.map(|p| p.into_iter().collect());
+        sup.baselines = snap.baselines.into_iter().collect();
+        sup.trust_rate = snap.trust_rate;
+        sup.trace = snap.trace;
+        sup.eviction = snap.eviction;
+        sup.cohorts = snap.cohorts;
+        sup.fingerprint.take();
+        sup.restore(snap.state);
+        Ok(sup)
+    }
+
+    /// Export only the intents changed or removed since `since`, plus a new token.
+    ///
+    /// Pass `CheckpointToken::ORIGIN` for a full base, then each returned `token` to the
+    /// next call; applying the chain in order with `apply_delta` reproduces the state.
+    /// Cost is proportional to the number of changes, not the number of intents.
+    pub fn snapshot_delta(&self, since: CheckpointToken) -> SupervisorDelta {
+        let token = self.generation.fetch_add(1, Ordering::SeqCst);
+
+        let mut changed: Vec<(String, ArbiterState)> = Vec::new();
+        let mut removed: Vec<String> = Vec::new();
+        for shard in &self.state_shards {
+            let guard = shard
+                .lock()
+                .expect("arbiter supervisor shard mutex poisoned");
+            let after = (since.0.saturating_add(1), String::new());
+            for (_, id) in guard.changes.range(after..) {
+                match guard.states.get(id) {
+                    Some(state) => changed.push((id.clone(), state.clone())),
+                    None => removed.push(id.clone()),
+                }
+            }
+        }
+
+        changed.sort_by(|a, b| a.0.cmp(&b.0));
+        removed.sort();
+        let cohorts = self.pinned_cohorts(changed.iter().map(|(id, _)| id));
+        let text_memory = self.text_memories(changed.iter().map(|(id, _)| id));
+        SupervisorDelta {
+            since,
+            token: CheckpointToken(token),
+            changed,
+            removed,
+            clock: self.clock(),
+            source_trust: self.source_trust(),
+            cohorts,
+            text_memory,
+        }
+    }
+
+    /// Apply a `snapshot_delta` on top of the current state: removals, then changed
+    /// intents (overwriting). Clock and source trust are taken from the delta.
+    pub fn apply_delta(&self, delta: SupervisorDelta) -> RestoreStats {
+        self.clock.store(delta.clock, Ordering::Relaxed);
+        *self.trust.lock().expect("arbiter supervisor trust mutex poisoned") = delta.source_trust.into_iter().collect();
+        for intent_id in &delta.removed {
+            self.clear_intent(intent_id);
+        }
+        let stats = self.import_state_merge(delta.changed);
+        self.pin_cohorts(delta.cohorts);
+        self.put_text_memories(delta.text_memory);
+        stats
+    }
+
+    /// Forget removals recorded at or before `through`. Deltas requested from an older
+    /// token will then miss those removals; call once every consumer is past `through`.
+    pub fn prune_removed(&self, through: CheckpointToken) {
+        for shard in &self.state_shards {
+            let mut guard = shard
+                .lock()
+                .expect("arbiter supervisor shard mutex poisoned");
+            let shard = &mut *guard;
+            let stale: Vec<(u64, String)> = shard
+                .changes
+                .range(..(through.0.saturating_add(1), String::new()))
+                .filter(|(_, id)| !shard.states.contains_key(id))
+                .cloned()
+                .collect();
+            for key in stale {
+                shard.changed_at.remove(&key.1);
+                shard.changes.remove(&key);
+            }
+        }
     }
 
     /// Export all `(intent_id, ArbiterState)` pairs.
@@ -214,7 +768,9 @@ impl ArbiterSupervisor {
         }
 
         out.sort_by(|a, b| a.0.cmp(&b.0));
-        SupervisorSnapshot { states: out }
+        let cohorts = self.pinned_cohorts(out.iter().map(|(id, _)| id));
+        let text_memory = self.text_memories(out.iter().map(|(id, _)| id));
+        SupervisorSnapshot { states: out, clock: self.clock(), source_trust: self.source_trust(), cohorts, text_memory }
     }
 
     /// Import `(intent_id, ArbiterState)` pairs, overwriting any existing per-intent state.
@@ -229,19 +785,19 @@ impl ArbiterSupervisor {
             let mut guard = shard
                 .lock()
                 .expect("arbiter supervisor shard mutex poisoned");
-            guard.states.clear();
+            guard.clear(self.generation());
         }
 
         // 2) Re-insert into the current shard layout.
         let mut stats = RestoreStats::default();
         for (intent_id, state) in iter {
-            let idx = shard_index(&intent_id, self.shards);
+            let idx = self.shard_index(&intent_id);
             let mut guard = self.state_shards[idx]
                 .lock()
                 .expect("arbiter supervisor shard mutex poisoned");
 
             // After a clear, `insert` should never overwrite, but keep the accounting correct.
-            if guard.states.insert(intent_id, state).is_some() {
+            if guard.insert(intent_id, state, self.generation()).is_some() {
                 stats.overwritten += 1;
             }
             stats.applied += 1;
@@ -259,12 +815,12 @@ impl ArbiterSupervisor {
     {
         let mut stats = RestoreStats::default();
         for (intent_id, state) in iter {
-            let idx = shard_index(&intent_id, self.shards);
+            let idx = self.shard_index(&intent_id);
             let mut guard = self.state_shards[idx]
                 .lock()
                 .expect("arbiter supervisor shard mutex poisoned");
 
-            if guard.states.insert(intent_id, state).is_some() {
+            if guard.insert(intent_id, state, self.generation()).is_some() {
                 stats.overwritten += 1;
             }
             stats.applied += 1;
@@ -274,19 +830,301 @@ impl ArbiterSupervisor {
 
     /// Clear a single intent's state (useful for ops / debugging).
     pub fn clear_intent(&self, intent_id: &str) {
-        let idx = shard_index(intent_id, self.shards);
-        let mut guard = self.state_shards[idx]
-            .lock()
-            .expect("arbiter supervisor shard mutex poisoned");
-        guard.states.remove(intent_id);
+        let mut shard = self.state_for_mut(intent_id);
+        shard.remove(intent_id, self.generation());
+        drop(shard);
+        if let Some(s) = &self.shadow {
+            s.lock().expect("arbiter supervisor shadow mutex poisoned").states.remove(intent_id);
+        }
+    }
+
+    /// Bound per-intent state (see `EvictionCfg`). Limits are enforced at the end of every
+    /// ingest, or on demand with `evict_idle`.
+    pub fn set_eviction(&mut self, eviction: EvictionCfg) {
+        self.fingerprint.take();
+        self.eviction = eviction;
+    }
+
+    /// Call `hook` with every evicted intent and its final state.
+    pub fn set_eviction_hook(&mut self, hook: impl Fn(String, ArbiterState) + Send + Sync + 'static) {
+        self.on_evict = Some(Box::new(hook));
+    }
+
+    pub fn clear_eviction_hook(&mut self) {
+        self.on_evict = None;
     }
 
-    fn cfg_for(&self, intent_id: &str) -> &ArbiterCfg {
-        self.cfg_overrides.get(intent_id).unwrap_or(&self.cfg)
+    /// Enforce the eviction limits at the current logical clock, without ingesting.
+    /// Returns the number of intents evicted.
+    pub fn evict_idle(&self) -> usize {
+        self.evict_at(self.clock())
+    }
+
+    /// Current intent count and eviction counters.
+    pub fn stats(&self) -> SupervisorStats {
+        let intents = self
+            .state_shards
+            .iter()
+            .map(|s| s.lock().expect("arbiter supervisor shard mutex poisoned").states.len())
+            .sum();
+        SupervisorStats {
+            intents,
+            evicted_capacity: self.evicted_capacity.load(Ordering::Relaxed),
+            evicted_idle: self.evicted_idle.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Current shard count.
+    pub fn shard_count(&self) -> usize {
+        self.shards
+    }
+
+    /// Change the shard count, moving every intent's state to the shard the strategy
+    /// now assigns it. Nothing is dropped; returns the number of intents that moved.
+    pub fn reshard(&mut self, new_count: usize) -> usize {
+        let new_count = new_count.max(1);
+        let old = std::mem::take(&mut self.state_shards);
+
+        let mut shards: Vec<Shard> = (0..new_count).map(|_| Shard::default()).collect();
+        let mut moved = 0;
+        for (old_idx, shard) in old.into_iter().enumerate() {
+            let shard = shard.into_inner().expect("arbiter supervisor shard mutex poisoned");
+            for (intent_id, state) in shard.states {
+                let idx = self.strategy.shard_for(&intent_id, new_count);
+                if idx != old_idx {
+                    moved += 1;
+                }
+                shards[idx].place(intent_id, state);
+            }
+            for (intent_id, sources) in shard.escalated_sources {
+                let idx = self.strategy.shard_for(&intent_id, new_count);
+                shards[idx].escalated_sources.insert(intent_id, sources);
+            }
+            for (intent_id, gen) in shard.changed_at {
+                let idx = self.strategy.shard_for(&intent_id, new_count);
+                shards[idx].touch(&intent_id, gen);
+            }
+            for (intent_id, cohort) in shard.cohorts {
+                let idx = self.strategy.shard_for(&intent_id, new_count);
+                shards[idx].cohorts.insert(intent_id, cohort);
+            }
+            for (intent_id, memories) in shard.texts {
+                let idx = self.strategy.shard_for(&intent_id, new_count);
+                shards[idx].texts.insert(intent_id, memories);
+            }
+        }
+
+        self.shards = new_count;
+        self.state_shards = shards.into_iter().map(std::sync::Mutex::new).collect();
+        moved
+    }
+
+    /// Decide intents with cfg variants assigned by `intent_id` hash (see `cohort`).
+    ///
+    /// An intent is pinned to its cohort on its first decision and keeps it, through
+    /// snapshots and restores, while it has state; pins naming a cohort no longer in
+    /// `cohorts` are reassigned. Per-intent cfg overrides still take precedence.
+    pub fn set_cohorts(&mut self, cohorts: Cohorts) {
+        self.fingerprint.take();
+        self.cohorts = Some(cohorts);
+    }
+
+    /// Decide every intent with the base cfg again. Pins are kept, so setting the same
+    /// cohorts later restores the assignments.
+    pub fn clear_cohorts(&mut self) {
+        self.fingerprint.take();
+        self.cohorts = None;
+    }
+
+    pub fn cohorts(&self) -> Option<&Cohorts> {
+        self.cohorts.as_ref()
+    }
+
+    /// Cohort `intent_id` is pinned to, or would be assigned on its next decision.
+    pub fn cohort_of(&self, intent_id: &str) -> Option<String> {
+        let cohorts = self.cohorts.as_ref()?;
+        let shard = self.state_for_mut(intent_id);
+        match shard.cohorts.get(intent_id) {
+            Some(c) if cohorts.contains(c) => Some(c.clone()),
+            _ => Some(cohorts.assign(intent_id).to_string()),
+        }
+    }
+
+    /// Record every ingest batch from now on (see `journal`). No-op if already enabled.
+    pub fn enable_journal(&mut self) {
+        self.journal.get_or_insert_with(Default::default);
+    }
+
+    /// Stop journaling and return what was recorded since the last `take_journal`.
+    pub fn disable_journal(&mut self) -> Option<Journal> {
+        self.journal
+            .take()
+            .map(|j| j.into_inner().expect("arbiter supervisor journal mutex poisoned"))
+    }
+
+    /// Drain the batches recorded so far; journaling continues. Empty if disabled.
+    pub fn take_journal(&self) -> Journal {
+        match &self.journal {
+            Some(j) => std::mem::take(&mut *j.lock().expect("arbiter supervisor journal mutex poisoned")),
+            None => Journal::default(),
+        }
+    }
+
+    /// Decide every ingest batch against `cfg` as well, starting from a copy of the live
+    /// states (see `shadow`). Replaces any previous shadow.
+    pub fn set_shadow_cfg(&mut self, cfg: ArbiterCfg) {
+        let states = self.export_state().states;
+        self.set_shadow(cfg, states);
+    }
+
+    /// Shadow `cfg` starting from the given states instead of the live ones, e.g. a
+    /// snapshot taken under the candidate cfg.
+    pub fn set_shadow<I>(&mut self, cfg: ArbiterCfg, states: I)
+    where
+        I: IntoIterator<Item = (String, ArbiterState)>,
+    {
+        let shadow = Shadow::new(cfg, states.into_iter().collect());
+        self.shadow = Some(std::sync::Mutex::new(shadow));
+    }
+
+    /// Stop shadowing; returns the final agreement counters.
+    pub fn clear_shadow(&mut self) -> Option<ShadowStats> {
+        self.shadow
+            .take()
+            .map(|s| s.into_inner().expect("arbiter supervisor shadow mutex poisoned").stats)
+    }
+
+    /// Candidate cfg being shadowed, if any.
+    pub fn shadow_cfg(&self) -> Option<ArbiterCfg> {
+        self.shadow
+            .as_ref()
+            .map(|s| s.lock().expect("arbiter supervisor shadow mutex poisoned").cfg.clone())
+    }
+
+    /// Agreement counters since the shadow was set.
+    pub fn shadow_stats(&self) -> Option<ShadowStats> {
+        self.shadow
+            .as_ref()
+            .map(|s| s.lock().expect("arbiter supervisor shadow mutex poisoned").stats)
+    }
+
+    /// Drain the reports of batches with a disagreement recorded so far, oldest first
+    /// (at most `MAX_SHADOW_REPORTS`). Empty if not shadowing.
+    pub fn take_shadow_reports(&self) -> Vec<ShadowTick> {
+        match &self.shadow {
+            Some(s) => std::mem::take(&mut s.lock().expect("arbiter supervisor shadow mutex poisoned").reports).into(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Stable hash of everything that shapes decisions: base cfg, overrides, source
+    /// profiles, registered baselines, trust rate, eviction limits and cohorts. Learned state and
+    /// the trace flag are excluded. Stable across runs of the same build. Computed once
+    /// and cached until one of those settings changes.
+    pub fn cfg_fingerprint(&self) -> u64 {
+        *self.fingerprint.get_or_init(|| {
+            let profiles = self.profiles.as_ref().map(sorted_entries);
+            fnv1a_u64(&format!(
+                "{:?}",
+                (
+                    &self.cfg,
+                    sorted_entries(&self.cfg_overrides),
+                    profiles,
+                    sorted_entries(&self.baselines),
+                    self.trust_rate,
+                    self.eviction,
+                    &self.cohorts,
+                )
+            ))
+        })
+    }
+
+    /// Current logical clock.
+    pub fn clock(&self) -> u64 {
+        self.clock.load(Ordering::Relaxed)
+    }
+
+    /// Generation to stamp on a change. Read with the affected shard locked, so a
+    /// concurrent `snapshot_delta` sees the change now or in its next delta.
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Override, else cohort variant, else base cfg.
+    fn cfg_for(&self, intent_id: &str, cohort: Option<&str>) -> &ArbiterCfg {
+        if let Some(cfg) = self.cfg_overrides.get(intent_id) {
+            return cfg;
+        }
+        cohort
+            .and_then(|c| self.cohorts.as_ref()?.cfg(c))
+            .unwrap_or(&self.cfg)
+    }
+
+    /// Pinned cohort of `intent_id`, assigning and pinning one if needed.
+    fn assign_cohort(&self, shard: &mut Shard, intent_id: &str) -> Option<String> {
+        let cohorts = self.cohorts.as_ref()?;
+        if let Some(c) = shard.cohorts.get(intent_id) {
+            if cohorts.contains(c) {
+                return Some(c.clone());
+            }
+        }
+        let c = cohorts.assign(intent_id).to_string();
+        shard.cohorts.insert(intent_id.to_string(), c.clone());
+        Some(c)
+    }
+
+    /// Pinned cohorts of those `ids` that have one, in the given order.
+    fn pinned_cohorts<'a>(&self, ids: impl Iterator<Item = &'a String>) -> Vec<(String, String)> {
+        ids.filter_map(|id| {
+            let shard = self.state_for_mut(id);
+            shard.cohorts.get(id).map(|c| (id.clone(), c.clone()))
+        })
+        .collect()
+    }
+
+    /// Restore pins for intents that have state.
+    fn pin_cohorts(&self, pins: Vec<(String, String)>) {
+        for (intent_id, cohort) in pins {
+            let mut shard = self.state_for_mut(&intent_id);
+            if shard.states.contains_key(&intent_id) {
+                shard.cohorts.insert(intent_id, cohort);
+            }
+        }
+    }
+
+    /// Text memory of those `ids` that have one, in the given order, then by source.
+    fn text_memories<'a>(&self, ids: impl Iterator<Item = &'a String>) -> Vec<(String, String, TextMemory)> {
+        let mut out = Vec::new();
+        for id in ids {
+            let shard = self.state_for_mut(id);
+            for (source_id, m) in shard.texts.get(id).into_iter().flatten() {
+                out.push((id.clone(), source_id.clone(), m.clone()));
+            }
+        }
+        out
+    }
+
+    /// Restore text memory for intents that have state.
+    fn put_text_memories(&self, memories: Vec<(String, String, TextMemory)>) {
+        for (intent_id, source_id, memory) in memories {
+            let mut shard = self.state_for_mut(&intent_id);
+            if shard.states.contains_key(&intent_id) {
+                shard.texts.entry(intent_id).or_default().insert(source_id, memory);
+            }
+        }
+    }
+
+    /// Whether `source_id`'s text is checked for freeze flags (`SourceProfile::freeze_eligible`).
+    fn freeze_eligible(&self, source_id: &str) -> bool {
+        self.profiles.as_ref().and_then(|p| p.get(source_id)).is_none_or(|p| p.freeze_eligible)
+    }
+
+    fn shard_index(&self, intent_id: &str) -> usize {
+        self.strategy.shard_for(intent_id, self.shards)
     }
 
     fn state_for_mut(&self, intent_id: &str) -> std::sync::MutexGuard<'_, Shard> {
-        let idx = shard_index(intent_id, self.shards);
+        let idx = self.shard_index(intent_id);
         self.state_shards[idx]
             .lock()
             .expect("arbiter supervisor shard mutex poisoned")
@@ -294,8 +1132,50 @@ impl ArbiterSupervisor {
 
     /// Ingest a batch of outside-world events and return escalation actions.
     ///
+    /// Each call advances the logical clock by one unit.
     /// This is deterministic for a given input ordering + shard count.
     pub fn ingest<B: EvidenceBuilder>(&self, builder: &B, events: &[SignalEvent<'_>]) -> Vec<ActionEvent> {
+        let now = self.clock.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        self.ingest_inner(builder, events, now, false)
+    }
+
+    /// Ingest a batch at caller-supplied logical time `now` (ticks, milliseconds, ...).
+    ///
+    /// The supervisor clock moves forward to `now`; it never moves backwards.
+    pub fn ingest_at<B: EvidenceBuilder>(&self, builder: &B, events: &[SignalEvent<'_>], now: u64) -> Vec<ActionEvent> {
+        self.clock.fetch_max(now, Ordering::Relaxed);
+        self.ingest_inner(builder, events, now, false)
+    }
+
+    /// `ingest`, deciding shards concurrently on scoped worker threads.
+    ///
+    /// Evidence building and grouping stay on the calling thread; each shard's intents
+    /// are then decided on a worker (at most `available_parallelism` of them). The output
+    /// is identical to `ingest` for the same input and state. Worth it for large batches
+    /// spread over several shards; small batches are cheaper serially.
+    pub fn ingest_parallel<B: EvidenceBuilder>(&self, builder: &B, events: &[SignalEvent<'_>]) -> Vec<ActionEvent> {
+        let now = self.clock.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        self.ingest_inner(builder, events, now, true)
+    }
+
+    /// `ingest_at`, deciding shards concurrently (see `ingest_parallel`).
+    pub fn ingest_parallel_at<B: EvidenceBuilder>(
+        &self,
+        builder: &B,
+        events: &[SignalEvent<'_>],
+        now: u64,
+    ) -> Vec<ActionEvent> {
+        self.clock.fetch_max(now, Ordering::Relaxed);
+        self.ingest_inner(builder, events, now, true)
+    }
+
+    fn ingest_inner<B: EvidenceBuilder>(
+        &self,
+        builder: &B,
+        events: &[SignalEvent<'_>],
+        now: u64,
+        parallel: bool,
+    ) -> Vec<ActionEvent> {
         // 1) Build evidence records.
         let evidence = build_evidence_batch(builder, events);
 
@@ -308,76 +1188,253 @@ impl ArbiterSupervisor {
                 .push(ev);
         }
 
-        // 3) Compute optional freeze flags per intent from text payloads.
-        //    We OR flags across all text entries for that intent.
-        let mut ff_by_intent: HashMap<String, FreezeFlags> = HashMap::new();
+        // 3) Group text payloads per intent and source. Sources that are not
+        //    `freeze_eligible` are skipped; only intents being decided are kept.
+        let mut texts_by_intent: HashMap<&str, BTreeMap<&str, Vec<&str>>> = HashMap::new();
         for se in events {
             if let Some(t) = &se.text {
-                let ff = freeze_flags(t);
-                let e = ff_by_intent.entry(se.intent_id.to_string()).or_default();
-                e.rep_3p |= ff.rep_3p;
-                e.stall |= ff.stall;
-                e.ai_tell |= ff.ai_tell;
+                if !self.freeze_eligible(&se.source_id) || !views.contains_key(se.intent_id.as_ref()) {
+                    continue;
+                }
+                let texts = texts_by_intent.entry(se.intent_id.as_ref()).or_default();
+                texts.entry(se.source_id.as_ref()).or_default().push(t.as_ref());
             }
         }
 
-        // 4) Apply source profiles (weights) if present.
+        // 3b) Detect freeze flags with the intent's own cfg (override, then cohort, then
+        //     base), OR-ed across one source's texts only, and compare payloads with that
+        //     source's recent ones for the intent (`cross_tick_repeat`). One lock per shard;
+        //     the cohort pinned here is the one the decision uses.
+        type SourceTexts<'t> = (&'t str, BTreeMap<&'t str, Vec<&'t str>>);
+        let mut by_shard: Vec<Vec<SourceTexts<'_>>> = (0..self.shards).map(|_| Vec::new()).collect();
+        for (intent_id, sources) in texts_by_intent {
+            by_shard[self.shard_index(intent_id)].push((intent_id, sources));
+        }
+        let mut ff_by_intent: HashMap<String, BTreeMap<String, FreezeFlags>> = HashMap::new();
+        for (idx, items) in by_shard.into_iter().enumerate().filter(|(_, items)| !items.is_empty()) {
+            let mut shard = self.state_shards[idx].lock().expect("arbiter supervisor shard mutex poisoned");
+            for (intent_id, sources) in items {
+                let cohort = self.assign_cohort(&mut shard, intent_id);
+                let freeze = &self.cfg_for(intent_id, cohort.as_deref()).freeze;
+                let by_source = ff_by_intent.entry(intent_id.to_string()).or_default();
+                for (source_id, texts) in sources {
+                    let mut ff = FreezeFlags::default();
+                    for t in &texts {
+                        ff = ff.union(freeze_flags_cfg(t, freeze));
+                    }
+                    if freeze.recent_texts > 0 {
+                        let memory = shard.texts.entry(intent_id.to_string()).or_default();
+                        ff.cross_tick_repeat = memory.entry(source_id.to_string()).or_default().observe(texts, freeze);
+                    }
+                    by_source.insert(source_id.to_string(), ff);
+                }
+            }
+        }
+        let source_flags = |id: &str| -> Vec<(String, FreezeFlags)> {
+            ff_by_intent.get(id).map(|m| m.iter().map(|(s, ff)| (s.clone(), *ff)).collect()).unwrap_or_default()
+        };
+
+        // 4) Apply source profiles (weights) if present, shifted by learned trust.
         if let Some(p) = &self.profiles {
+            let trust = self.trust.lock().expect("arbiter supervisor trust mutex poisoned");
             for view in views.values_mut() {
-                apply_source_profiles(view, p);
+                apply_source_profiles_with_trust(view, p, &trust);
             }
         }
 
+        // The shadow sees the same views and flags, sorted like the final output.
+        let shadow_work: Option<Vec<ShardWork>> = self.shadow.as_ref().map(|_| {
+            let mut w: Vec<ShardWork> = views
+                .iter()
+                .map(|(id, view)| (id.clone(), view.clone(), source_flags(id)))
+                .collect();
+            w.sort_by(|a, b| a.0.cmp(&b.0));
+            w
+        });
+
         // 5) Group intents by shard to avoid lock-per-intent.
         // Determinism: we sort intent ids within each shard and also sort final outputs by intent_id.
-        let mut shard_intents: Vec<Vec<String>> = vec![Vec::new(); self.shards];
-        for intent_id in views.keys() {
-            let idx = shard_index(intent_id, self.shards);
-            shard_intents[idx].push(intent_id.clone());
+        let mut work: Vec<Vec<ShardWork>> = (0..self.shards).map(|_| Vec::new()).collect();
+        for (intent_id, view) in views {
+            let ff = source_flags(&intent_id);
+            work[self.shard_index(&intent_id)].push((intent_id, view, ff));
         }
-        for v in &mut shard_intents {
-            v.sort();
+        for v in &mut work {
+            v.sort_by(|a, b| a.0.cmp(&b.0));
         }
 
-        // 6) Decide per shard (lock each shard once).
-        let mut out: Vec<ActionEvent> = Vec::with_capacity(views.len());
-        for (shard_idx, intents) in shard_intents.into_iter().enumerate() {
-            if intents.is_empty() {
-                continue;
-            }
+        // 6) Decide per shard (lock each shard once), optionally one worker per shard group.
+        let mut out: Vec<ActionEvent> = if parallel {
+            self.decide_parallel(work, now)
+        } else {
+            work.into_iter()
+                .enumerate()
+                .flat_map(|(shard_idx, items)| self.decide_shard(shard_idx, items, now))
+                .collect()
+        };
+
+        // Preserve the original API behavior: return actions sorted by intent_id.
+        out.sort_by(|a, b| a.intent_id.cmp(&b.intent_id));
 
-            let mut guard = self.state_shards[shard_idx]
+        // 7) Decide the shadow cfg against the live decisions; live traces were only
+        //    forced on for the report.
+        if let (Some(shadow), Some(work)) = (&self.shadow, shadow_work) {
+            shadow
                 .lock()
-                .expect("arbiter supervisor shard mutex poisoned");
+                .expect("arbiter supervisor shadow mutex poisoned")
+                .tick(now, work, &out, &self.cfg_overrides, &self.baselines);
+            if !self.trace {
+                for a in &mut out {
+                    a.trace = None;
+                }
+            }
+        }
 
-            for intent_id in intents {
-                let view = views.remove(&intent_id).expect("view existed");
-                let ff = ff_by_intent.get(&intent_id).copied();
+        // 8) Enforce TTL / capacity limits.
+        self.evict_at(now);
 
-                let state = guard.states.entry(intent_id.clone()).or_default();
+        // 9) Journal the batch.
+        if let Some(journal) = &self.journal {
+            let entry = JournalEntry {
+                now,
+                cfg_fingerprint: self.cfg_fingerprint(),
+                events: events.iter().map(JournalEvent::from_signal).collect(),
+                actions: out.iter().map(JournalAction::from).collect(),
+            };
+            journal.lock().expect("arbiter supervisor journal mutex poisoned").entries.push(entry);
+        }
+        out
+    }
+
+    /// Apply the eviction limits at logical time `now`; returns the number evicted.
+    fn evict_at(&self, now: u64) -> usize {
+        if self.eviction == EvictionCfg::default() {
+            return 0;
+        }
+        let mut evicted = Vec::new();
+        for shard in &self.state_shards {
+            let mut guard = shard.lock().expect("arbiter supervisor shard mutex poisoned");
+            guard.evict(self.eviction, now, self.generation(), &mut evicted);
+        }
+
+        // Hook runs without any shard locked.
+        let n = evicted.len();
+        if let Some(s) = &self.shadow {
+            let mut shadow = s.lock().expect("arbiter supervisor shadow mutex poisoned");
+            for (intent_id, _, _) in &evicted {
+                shadow.states.remove(intent_id);
+            }
+        }
+        for (intent_id, state, by_capacity) in evicted {
+            let counter = if by_capacity { &self.evicted_capacity } else { &self.evicted_idle };
+            counter.fetch_add(1, Ordering::Relaxed);
+            if let Some(hook) = &self.on_evict {
+                hook(intent_id, state);
+            }
+        }
+        n
+    }
 
-                // If we have freeze flags, bump hysteresis first.
-                if let Some(flags) = ff {
-                    state.bump(flags, self.cfg_for(&intent_id).hyst_disable);
+    /// Decide one shard's intents (sorted by `intent_id`) under a single lock.
+    fn decide_shard(&self, shard_idx: usize, items: Vec<ShardWork>, now: u64) -> Vec<ActionEvent> {
+        let mut out: Vec<ActionEvent> = Vec::with_capacity(items.len());
+        if items.is_empty() {
+            return out;
+        }
+
+        let mut guard = self.state_shards[shard_idx]
+            .lock()
+            .expect("arbiter supervisor shard mutex poisoned");
+        let shard = &mut *guard;
+        let gen = self.generation();
+
+        for (intent_id, view, ff) in items {
+            let cohort = self.assign_cohort(shard, &intent_id);
+            let cfg = self.cfg_for(&intent_id, cohort.as_deref());
+            shard.touch(&intent_id, gen);
+            let state = match shard.states.get_mut(&intent_id) {
+                Some(state) => {
+                    shard.lru.remove(&(Shard::recency(state), intent_id.clone()));
+                    state
                 }
+                None => shard.states.entry(intent_id.clone()).or_default(),
+            };
+            state.advance(now, cfg);
+            shard.lru.insert((Shard::recency(state), intent_id.clone()));
+
+            // Registered baselines win over learned ones.
+            let learned = state.learned_baselines(cfg);
+            let baselines = self.baselines.get(&intent_id).or(learned.as_ref());
 
-                // Core decision.
-                let esc = arbiter_idle_tick(&view, ff, self.cfg_for(&intent_id), state);
+            // Core decision; it also bumps per-source hysteresis from `ff` (exactly once).
+            let (esc, trace) = if self.trace || self.shadow.is_some() {
+                let t = arbiter_persona_tick_sources_traced(&view, &ff, baselines, cfg, state);
+                (t.escalation, Some(t))
+            } else {
+                (arbiter_persona_tick_sources(&view, &ff, baselines, cfg, state), None)
+            };
 
-                // Telemetry is optional; compute once.
-                let u = Some(view.to_uncertainty());
+            // Telemetry is optional; compute once.
+            let u = Some(view.to_uncertainty_persona(cfg, baselines));
+            state.learn(&view, cfg);
 
-                out.push(ActionEvent {
-                    intent_id,
-                    escalation: esc,
-                    uncertainty: u,
-                    freeze_flags: ff,
-                });
+            if esc != Escalation::None {
+                let mut sources: Vec<String> = view
+                    .evidence
+                    .iter()
+                    .filter(|ev| ev.weight > 0.0)
+                    .map(|ev| ev.source_id.clone())
+                    .collect();
+                sources.sort();
+                sources.dedup();
+                shard.escalated_sources.insert(intent_id.clone(), sources);
             }
-        }
 
-        // Preserve the original API behavior: return actions sorted by intent_id.
-        out.sort_by(|a, b| a.intent_id.cmp(&b.intent_id));
+            out.push(ActionEvent {
+                intent_id,
+                escalation: esc,
+                held: state.last_hold,
+                uncertainty: u,
+                freeze_flags: union_flags(&ff),
+                source_flags: ff.into_iter().filter(|(_, f)| f.any()).collect(),
+                trace,
+                cohort,
+            });
+        }
         out
     }
+
+    /// Run `decide_shard` for every shard, spread over scoped worker threads.
+    fn decide_parallel(&self, work: Vec<Vec<ShardWork>>, now: u64) -> Vec<ActionEvent> {
+        let busy: Vec<(usize, Vec<ShardWork>)> =
+            work.into_iter().enumerate().filter(|(_, items)| !items.is_empty()).collect();
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get()).min(busy.len());
+        if workers <= 1 {
+            return busy.into_iter().flat_map(|(idx, items)| self.decide_shard(idx, items, now)).collect();
+        }
+
+        // Shard `i` goes to worker `i % workers`; each shard is still decided under its own lock.
+        let mut groups: Vec<Vec<(usize, Vec<ShardWork>)>> = (0..workers).map(|_| Vec::new()).collect();
+        for (i, shard) in busy.into_iter().enumerate() {
+            groups[i % workers].push(shard);
+        }
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = groups
+                .into_iter()
+                .map(|group| {
+                    scope.spawn(move || {
+                        group
+                            .into_iter()
+                            .flat_map(|(idx, items)| self.decide_shard(idx, items, now))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("arbiter supervisor worker panicked"))
+                .collect()
+        })
+    }
 }
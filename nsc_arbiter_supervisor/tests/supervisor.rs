@@ -0,0 +1,756 @@
+use nsc_arbiter_core::{
+    Aggregation, AggregationCfg, ArbiterCfg, BaselineEstimator, BaselineLearning, Direction, Escalation, FreezeFlags, Hold,
+    HystDecay, MetricSpec, MetricValue, PauseReason, PersonaBaselines, Predicate, SourceProfile, SourceProfiles,
+};
+use nsc_arbiter_supervisor::{
+    replay, Cohorts, DivergenceKind, Journal, CONTROL_COHORT,
+    ActionEvent, ArbiterSupervisor, BasicEvidenceBuilder, CheckpointToken, EvictionCfg, FnvModulo, JumpHash, Rendezvous, ShardStrategy, SignalEvent,
+    ShadowStats, SnapshotError, SupervisorSnapshotV2, SupervisorStats, SNAPSHOT_SCHEMA_VERSION,
+};
+
+#[test]
+fn trace_is_opt_in() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![SignalEvent::new("intent-1", "llm", "decoder")
+        .with_scalar("entropy", 3.0)
+        .with_scalar("cosine", 0.9)];
+
+    let mut sup = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    let out = sup.ingest(&builder, &events);
+    assert!(out[0].trace.is_none());
+
+    sup.set_trace(true);
+    let out = sup.ingest(&builder, &events);
+    let trace = out[0].trace.as_ref().expect("trace enabled");
+    assert_eq!(trace.escalation, out[0].escalation);
+    assert_eq!(out[0].escalation, Escalation::CritiquePass);
+    assert_eq!(trace.fired_mask(), Predicate::HiEntropy.bit());
+}
+
+#[test]
+fn cooldown_and_decay_survive_restore() {
+    let builder = BasicEvidenceBuilder::default();
+    let noisy = vec![SignalEvent::new("intent-1", "llm", "decoder")
+        .with_scalar("entropy", 3.0)
+        .with_scalar("cosine", 0.9)];
+    let cfg = ArbiterCfg {
+        cooldown_time: 10,
+        hyst_decay: HystDecay::Exponential { half_life: 5.0 },
+        ..ArbiterCfg::default()
+    };
+
+    let sup = ArbiterSupervisor::new(2, cfg.clone());
+    assert_eq!(sup.ingest_at(&builder, &noisy, 100)[0].escalation, Escalation::CritiquePass);
+    let snap = sup.snapshot();
+    assert_eq!(snap.clock, 100);
+
+    let restored = ArbiterSupervisor::new(2, cfg);
+    restored.restore(snap.clone());
+    assert_eq!(restored.snapshot().states, snap.states);
+    assert_eq!(restored.clock(), 100);
+
+    // Still inside the 10-unit cool-down, then clear of it.
+    assert_eq!(restored.ingest_at(&builder, &noisy, 105)[0].escalation, Escalation::None);
+    assert_eq!(restored.ingest_at(&builder, &noisy, 110)[0].escalation, Escalation::CritiquePass);
+}
+
+#[test]
+fn maturity_hold_is_reported() {
+    let builder = BasicEvidenceBuilder::default();
+    let cfg = ArbiterCfg { min_sources: 2, ..ArbiterCfg::default() };
+    let sup = ArbiterSupervisor::new(1, cfg);
+
+    let one = vec![SignalEvent::new("intent-1", "llm", "decoder")
+        .with_scalar("entropy", 3.0)
+        .with_scalar("cosine", 0.9)];
+    let out = sup.ingest(&builder, &one);
+    assert_eq!(out[0].escalation, Escalation::None);
+    assert_eq!(out[0].held, Some(Hold::MinSources));
+
+    let two = vec![
+        one[0].clone(),
+        SignalEvent::new("intent-1", "stt", "prosody")
+            .with_scalar("entropy", 3.0)
+            .with_scalar("cosine", 0.9),
+    ];
+    let out = sup.ingest(&builder, &two);
+    assert_eq!(out[0].escalation, Escalation::CritiquePass);
+    assert_eq!(out[0].held, None);
+}
+
+#[test]
+fn per_intent_aggregation_override() {
+    let builder = BasicEvidenceBuilder::default();
+    let mut events = vec![SignalEvent::new("intent-1", "vendor", "rules")
+        .with_scalar("cosine", 1.0)
+        .with_rule_hits(3)];
+    for src in ["llm", "stt", "health", "meta_onnx", "video_gen", "audio_gen"] {
+        events.push(SignalEvent::new("intent-1", src, "probe").with_scalar("cosine", 1.0));
+    }
+
+    let sup = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    let out = sup.ingest(&builder, &events);
+    assert_eq!(out[0].escalation, Escalation::CritiquePass);
+    assert_eq!(out[0].uncertainty.as_ref().unwrap().rule_hits, 3);
+
+    let mut sup = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    let aggregation = AggregationCfg { rule_hits: Aggregation::WeightedMean, ..AggregationCfg::default() };
+    sup.set_cfg_override("intent-1", ArbiterCfg { aggregation, ..ArbiterCfg::default() });
+    let out = sup.ingest(&builder, &events);
+    assert_eq!(out[0].escalation, Escalation::None);
+    assert_eq!(out[0].uncertainty.as_ref().unwrap().rule_hits, 0);
+}
+
+#[test]
+fn custom_scalars_feed_registered_metrics() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![
+        SignalEvent::new("intent-1", "llm", "decoder")
+            .with_scalar("cosine", 1.0)
+            .with_scalar("latency_ms", 120.0),
+        SignalEvent::new("intent-1", "probe", "health")
+            .with_scalar("cosine", 1.0)
+            .with_scalar("latency_ms", 900.0),
+    ];
+
+    let mut sup = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    assert_eq!(sup.ingest(&builder, &events)[0].escalation, Escalation::None);
+
+    sup.cfg_mut()
+        .metrics
+        .register(MetricSpec::new("latency_ms", Aggregation::Max, Direction::HigherIsWorse, 500.0));
+    sup.set_trace(true);
+    let out = sup.ingest(&builder, &events);
+    assert_eq!(out[0].escalation, Escalation::CritiquePass);
+    let u = out[0].uncertainty.as_ref().unwrap();
+    assert_eq!(u.metrics, vec![MetricValue::new("latency_ms", 900.0)]);
+    assert!(out[0].trace.as_ref().unwrap().metrics[0].fired);
+}
+
+#[test]
+fn persona_baselines_drive_oddity() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![
+        SignalEvent::new("alice", "llm", "decoder")
+            .with_scalar("entropy", 2.0)
+            .with_scalar("cosine", 0.8),
+        SignalEvent::new("bob", "llm", "decoder")
+            .with_scalar("entropy", 2.0)
+            .with_scalar("cosine", 0.8),
+    ];
+    let calm = PersonaBaselines {
+        entropy_mu: 1.0,
+        entropy_sigma: 0.1,
+        gate_shift_sigma: 0.1,
+        cos_dist_sigma: 0.05,
+        ..PersonaBaselines::default()
+    };
+
+    let mut sup = ArbiterSupervisor::new(2, ArbiterCfg { tau_oddity: 0.5, ..ArbiterCfg::default() });
+    sup.set_baselines("alice", calm);
+    let out = sup.ingest(&builder, &events);
+    assert_eq!(out[0].intent_id, "alice");
+    assert_eq!(out[0].escalation, Escalation::CritiquePass);
+    assert!(out[0].uncertainty.as_ref().unwrap().oddity > 0.5);
+    assert_eq!(out[1].escalation, Escalation::None);
+    assert_eq!(out[1].uncertainty.as_ref().unwrap().oddity, 0.0);
+
+    sup.clear_baselines("alice");
+    assert!(sup.baselines("alice").is_none());
+    assert_eq!(sup.ingest(&builder, &events)[0].escalation, Escalation::None);
+}
+
+#[test]
+fn learned_baselines_survive_restore() {
+    let builder = BasicEvidenceBuilder::default();
+    let tick = |entropy: f32| {
+        vec![SignalEvent::new("intent-1", "llm", "decoder")
+            .with_scalar("entropy", entropy)
+            .with_scalar("cosine", 0.95)]
+    };
+    let cfg = ArbiterCfg {
+        tau_oddity: 0.5,
+        baseline_learning: Some(BaselineLearning { estimator: BaselineEstimator::Ewma { half_life: 8.0 }, warmup: 4 }),
+        ..ArbiterCfg::default()
+    };
+
+    let sup = ArbiterSupervisor::new(1, cfg.clone());
+    for x in [1.0, 1.1, 0.9, 1.0] {
+        let out = sup.ingest(&builder, &tick(x));
+        assert_eq!(out[0].uncertainty.as_ref().unwrap().oddity, 0.0);
+    }
+
+    let snap = sup.snapshot();
+    assert!(snap.states[0].1.learned.is_some());
+    let restored = ArbiterSupervisor::new(1, cfg);
+    restored.restore(snap);
+
+    let out = restored.ingest(&builder, &tick(2.0));
+    assert!(out[0].uncertainty.as_ref().unwrap().oddity > 0.5);
+    assert_eq!(out[0].escalation, Escalation::CritiquePass);
+}
+
+#[test]
+fn outcome_feedback_learns_source_trust() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![
+        SignalEvent::new("intent-1", "vendor", "classifier")
+            .with_scalar("entropy", 3.0)
+            .with_scalar("cosine", 1.0),
+        SignalEvent::new("intent-1", "llm", "decoder")
+            .with_scalar("entropy", 1.0)
+            .with_scalar("cosine", 1.0),
+    ];
+    let mut profiles = SourceProfiles::new();
+    profiles.insert("vendor".to_string(), SourceProfile::new(0.6, 0.2, 0.8));
+    profiles.insert("llm".to_string(), SourceProfile::new(1.0, 0.5, 1.0));
+
+    let mut sup = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    sup.set_source_profiles(profiles);
+    sup.set_trust_rate(0.5);
+    assert_eq!(sup.report_outcome("intent-1", false), 0);
+
+    // Neutral trust: (vendor 0.8 * 3.0 + llm 1.0 * 1.0) / 1.8 = 1.89 entropy.
+    let cfg = ArbiterCfg { tau_e: 1.8, ..ArbiterCfg::default() };
+    sup.set_cfg_override("intent-1", cfg);
+    assert_eq!(sup.ingest(&builder, &events)[0].escalation, Escalation::CritiquePass);
+    assert_eq!(sup.report_outcome("intent-1", false), 2);
+    // Already reported.
+    assert_eq!(sup.report_outcome("intent-1", false), 0);
+
+    sup.ingest(&builder, &events);
+    sup.report_outcome("intent-1", false);
+    let trust = sup.source_trust();
+    assert_eq!(trust.len(), 2);
+    assert_eq!(trust[1].0, "vendor");
+    assert!((trust[1].1.reliability - -0.75).abs() < 1e-6);
+    assert_eq!(trust[1].1.not_useful, 2);
+
+    // Both sources lost trust; the noisy vendor lost more weight (0.8 -> 0.4) than the
+    // llm (1.0 -> 0.625), so entropy drops to 1.78.
+    let out = sup.ingest(&builder, &events);
+    assert_eq!(out[0].escalation, Escalation::None);
+
+    let restored = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    restored.restore(sup.snapshot());
+    assert_eq!(restored.source_trust(), trust);
+}
+
+#[test]
+fn consistent_strategies_move_few_intents() {
+    let ids: Vec<String> = (0..2000).map(|i| format!("intent-{i}")).collect();
+    let moved = |s: &dyn ShardStrategy, from: usize, to: usize| {
+        ids.iter().filter(|id| s.shard_for(id, from) != s.shard_for(id, to)).count()
+    };
+
+    for s in [&FnvModulo as &dyn ShardStrategy, &JumpHash, &Rendezvous] {
+        let mut counts = [0usize; 8];
+        for id in &ids {
+            let idx = s.shard_for(id, 8);
+            assert_eq!(idx, s.shard_for(id, 8));
+            counts[idx] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 150), "{s:?} unbalanced: {counts:?}");
+        assert_eq!(s.shard_for("intent-0", 1), 0);
+    }
+
+    // Growing 8 -> 9 shards: modulo reshuffles most intents, consistent hashing ~1/9.
+    assert!(moved(&FnvModulo, 8, 9) > 1500);
+    assert!(moved(&JumpHash, 8, 9) < 350);
+    assert!(moved(&Rendezvous, 8, 9) < 350);
+    // Jump hash only ever moves intents onto the new shard.
+    assert!(ids
+        .iter()
+        .filter(|id| JumpHash.shard_for(id, 8) != JumpHash.shard_for(id, 9))
+        .all(|id| JumpHash.shard_for(id, 9) == 8));
+}
+
+#[test]
+fn reshard_keeps_every_intent() {
+    let builder = BasicEvidenceBuilder::default();
+    let events: Vec<SignalEvent> = (0..50)
+        .map(|i| SignalEvent::new(format!("intent-{i}"), "llm", "decoder").with_scalar("entropy", 3.0))
+        .collect();
+
+    let mut sup = ArbiterSupervisor::with_shard_strategy(4, ArbiterCfg::default(), JumpHash);
+    sup.ingest(&builder, &events);
+    sup.report_outcome("intent-7", true);
+    sup.ingest(&builder, &events);
+    let before = sup.snapshot();
+
+    let moved = sup.reshard(7);
+    assert!(moved > 0 && moved < 50);
+    assert_eq!(sup.shard_count(), 7);
+    assert_eq!(sup.snapshot().states, before.states);
+    // Pending outcome feedback moved along with the state.
+    assert_eq!(sup.report_outcome("intent-7", true), 1);
+
+    // Shrinking works the same way.
+    let on_zero = before.states.iter().filter(|(id, _)| JumpHash.shard_for(id, 7) == 0).count();
+    assert_eq!(sup.reshard(1), 50 - on_zero);
+    assert_eq!(sup.snapshot().states, before.states);
+}
+
+#[test]
+fn parallel_ingest_matches_serial() {
+    let builder = BasicEvidenceBuilder::default();
+    let batch = |tick: u32| -> Vec<SignalEvent<'static>> {
+        (0..3000u32)
+            .map(|i| {
+                let ev = SignalEvent::new(format!("intent-{}", i % 700), format!("src-{}", i % 3), "bench")
+                    .with_scalar("entropy", ((i * 7 + tick * 13) % 40) as f32 / 10.0)
+                    .with_scalar("cosine", 0.5 + ((i + tick) % 5) as f32 / 10.0);
+                if i % 11 == 0 { ev.with_text("again again again again again again") } else { ev }
+            })
+            .collect()
+    };
+
+    let cfg = ArbiterCfg { cooldown_ticks: 2, ..ArbiterCfg::default() };
+    let mut serial = ArbiterSupervisor::new(8, cfg.clone());
+    let mut parallel = ArbiterSupervisor::new(8, cfg);
+    serial.set_trace(true);
+    parallel.set_trace(true);
+
+    for tick in 0..4 {
+        let events = batch(tick);
+        let a = serial.ingest(&builder, &events);
+        let b = parallel.ingest_parallel(&builder, &events);
+        assert_eq!(a.len(), 700);
+        assert_eq!(format!("{a:?}"), format!("{b:?}"));
+    }
+    assert_eq!(serial.snapshot().states, parallel.snapshot().states);
+    assert_eq!(serial.clock(), parallel.clock());
+}
+
+#[test]
+fn capacity_evicts_least_recent_intent() {
+    use std::sync::{Arc, Mutex};
+
+    let builder = BasicEvidenceBuilder::default();
+    let tick = |id: &'static str| vec![SignalEvent::new(id, "llm", "decoder").with_scalar("cosine", 1.0)];
+
+    let mut sup = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    sup.set_eviction(EvictionCfg { max_intents_per_shard: 2, idle_ttl: 0 });
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&evicted);
+    sup.set_eviction_hook(move |id, state| sink.lock().unwrap().push((id, state.last_ts)));
+
+    sup.ingest_at(&builder, &tick("a"), 1);
+    sup.ingest_at(&builder, &tick("b"), 2);
+    sup.ingest_at(&builder, &tick("a"), 3); // "a" is now more recent than "b"
+    sup.ingest_at(&builder, &tick("c"), 4);
+
+    assert_eq!(*evicted.lock().unwrap(), vec![("b".to_string(), Some(2))]);
+    let ids: Vec<_> = sup.snapshot().states.into_iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, vec!["a", "c"]);
+    assert_eq!(sup.stats(), SupervisorStats { intents: 2, evicted_capacity: 1, evicted_idle: 0 });
+}
+
+#[test]
+fn idle_intents_expire_after_ttl() {
+    let builder = BasicEvidenceBuilder::default();
+    let tick = |id: &'static str| vec![SignalEvent::new(id, "llm", "decoder").with_scalar("cosine", 1.0)];
+
+    let mut sup = ArbiterSupervisor::new(4, ArbiterCfg::default());
+    sup.set_eviction(EvictionCfg { max_intents_per_shard: 0, idle_ttl: 10 });
+
+    sup.ingest_at(&builder, &tick("old"), 100);
+    sup.ingest_at(&builder, &tick("fresh"), 105);
+    assert_eq!(sup.stats().intents, 2);
+
+    // 10 units idle is still within the TTL.
+    sup.ingest_at(&builder, &tick("fresh"), 110);
+    assert_eq!(sup.stats().intents, 2);
+
+    sup.ingest_at(&builder, &tick("fresh"), 111);
+    let ids: Vec<_> = sup.snapshot().states.into_iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, vec!["fresh"]);
+    assert_eq!(sup.stats().evicted_idle, 1);
+
+    // Manual sweep at the current clock.
+    assert_eq!(sup.evict_idle(), 0);
+}
+
+#[test]
+fn full_snapshot_rebuilds_identical_supervisor() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![
+        SignalEvent::new("intent-1", "vendor", "decoder")
+            .with_scalar("entropy", 2.4)
+            .with_scalar("cosine", 0.9),
+        SignalEvent::new("intent-2", "llm", "decoder")
+            .with_scalar("entropy", 2.4)
+            .with_scalar("cosine", 0.9),
+    ];
+
+    let mut sup = ArbiterSupervisor::with_shard_strategy(3, ArbiterCfg { tau_e: 1.5, ..ArbiterCfg::default() }, JumpHash);
+    sup.set_cfg_override("intent-2", ArbiterCfg { tau_e: 3.0, ..ArbiterCfg::default() });
+    sup.set_source_profile("vendor", SourceProfile::new(0.6, 0.2, 0.8));
+    sup.set_baselines("intent-1", PersonaBaselines::default());
+    sup.set_trust_rate(0.3);
+    sup.set_trace(true);
+    sup.set_eviction(EvictionCfg { max_intents_per_shard: 8, idle_ttl: 50 });
+    sup.ingest_at(&builder, &events, 10);
+
+    let snap = sup.snapshot_full();
+    assert_eq!(snap.schema_version, SNAPSHOT_SCHEMA_VERSION);
+    assert_eq!(snap.shard_strategy.as_deref(), Some("jump_hash"));
+
+    let restored = ArbiterSupervisor::restore_full(snap.clone()).expect("restore");
+    assert_eq!(restored.snapshot_full(), snap);
+
+    let a = format!("{:?}", sup.ingest_at(&builder, &events, 11));
+    let b = format!("{:?}", restored.ingest_at(&builder, &events, 11));
+    assert_eq!(a, b);
+    assert!(a.contains("CritiquePass"));
+}
+
+#[test]
+fn full_snapshot_migrates_and_rejects_versions() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![SignalEvent::new("intent-1", "llm", "decoder").with_scalar("cosine", 1.0)];
+    let cfg = ArbiterCfg { tau_e: 1.5, ..ArbiterCfg::default() };
+
+    let sup = ArbiterSupervisor::new(1, cfg.clone());
+    sup.ingest_at(&builder, &events, 7);
+
+    let v2 = SupervisorSnapshotV2::from_v1(sup.snapshot(), cfg.clone());
+    let restored = ArbiterSupervisor::restore_full(v2.clone()).expect("restore");
+    assert_eq!(restored.cfg(), &cfg);
+    assert_eq!(restored.snapshot(), sup.snapshot());
+
+    let newer = SupervisorSnapshotV2 { schema_version: SNAPSHOT_SCHEMA_VERSION + 1, ..v2.clone() };
+    assert_eq!(
+        ArbiterSupervisor::restore_full(newer).err(),
+        Some(SnapshotError::UnsupportedVersion(SNAPSHOT_SCHEMA_VERSION + 1))
+    );
+
+    let custom = SupervisorSnapshotV2 { shard_strategy: None, ..v2 };
+    assert_eq!(ArbiterSupervisor::restore_full(custom.clone()).err(), Some(SnapshotError::CustomShardStrategy));
+    assert!(ArbiterSupervisor::restore_full_with_strategy(custom, Rendezvous).is_ok());
+}
+
+#[test]
+fn delta_chain_reproduces_state() {
+    let builder = BasicEvidenceBuilder::default();
+    let tick = |id: &'static str| vec![SignalEvent::new(id, "llm", "decoder").with_scalar("cosine", 1.0)];
+
+    let sup = ArbiterSupervisor::new(2, ArbiterCfg::default());
+    for id in ["a", "b", "c"] {
+        sup.ingest_at(&builder, &tick(id), 1);
+    }
+
+    let replica = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    let base = sup.snapshot_delta(CheckpointToken::ORIGIN);
+    assert_eq!(base.changed.len(), 3);
+    replica.apply_delta(base.clone());
+
+    // Nothing changed: an empty delta.
+    let idle = sup.snapshot_delta(base.token);
+    assert!(idle.changed.is_empty() && idle.removed.is_empty());
+    assert!(idle.token > base.token);
+
+    sup.ingest_at(&builder, &tick("b"), 2);
+    sup.clear_intent("c");
+    sup.ingest_at(&builder, &tick("d"), 3);
+
+    let delta = sup.snapshot_delta(idle.token);
+    let changed: Vec<_> = delta.changed.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(changed, vec!["b", "d"]);
+    assert_eq!(delta.removed, vec!["c"]);
+
+    replica.apply_delta(delta.clone());
+    assert_eq!(replica.snapshot(), sup.snapshot());
+
+    // Pruned removals drop out of deltas taken from older tokens.
+    sup.prune_removed(delta.token);
+    assert!(sup.snapshot_delta(idle.token).removed.is_empty());
+}
+
+#[test]
+fn journal_replays_deterministically() {
+    let builder = BasicEvidenceBuilder::default();
+    let cfg = ArbiterCfg { cooldown_ticks: 1, ..ArbiterCfg::default() };
+    let batch = |e: f32| {
+        vec![
+            SignalEvent::new("intent-1", "llm", "decoder")
+                .with_scalar("entropy", e)
+                .with_scalar("cosine", 0.9),
+            SignalEvent::new("intent-2", "llm", "decoder")
+                .with_text("the same words the same words the same words")
+                .with_scalar("cosine", 1.0),
+        ]
+    };
+
+    let mut sup = ArbiterSupervisor::new(2, cfg.clone());
+    sup.enable_journal();
+    sup.ingest(&builder, &batch(3.0));
+    sup.ingest(&builder, &batch(3.0));
+    sup.ingest_at(&builder, &batch(1.0), 9);
+
+    let journal = sup.take_journal();
+    assert_eq!(journal.len(), 3);
+    assert!(sup.take_journal().is_empty());
+    assert_eq!(Journal::decode(&journal.encode()), Ok(journal.clone()));
+
+    let fresh = ArbiterSupervisor::new(1, cfg);
+    let report = replay(&fresh, &builder, &journal);
+    assert!(report.is_faithful(), "{:?}", report.divergence);
+    assert_eq!(report.entries_replayed, 3);
+
+    // The cached fingerprint follows every setter.
+    let mut probe = ArbiterSupervisor::new(1, fresh.cfg().clone());
+    let base = probe.cfg_fingerprint();
+    assert_eq!(base, sup.cfg_fingerprint());
+    probe.cfg_mut().tau_e = 9.0;
+    assert_ne!(probe.cfg_fingerprint(), base);
+    probe.cfg_mut().tau_e = fresh.cfg().tau_e;
+    assert_eq!(probe.cfg_fingerprint(), base);
+    probe.set_cfg_override("intent-1", ArbiterCfg::default());
+    assert_ne!(probe.cfg_fingerprint(), base);
+    probe.clear_cfg_override("intent-1");
+    probe.set_baselines("intent-1", PersonaBaselines::default());
+    assert_ne!(probe.cfg_fingerprint(), base);
+    probe.clear_baselines("intent-1");
+    assert_eq!(probe.cfg_fingerprint(), base);
+
+    // Different configuration: caught before re-ingesting.
+    let other = ArbiterSupervisor::new(1, ArbiterCfg::default());
+    let report = replay(&other, &builder, &journal);
+    assert_eq!(report.entries_replayed, 0);
+    assert!(matches!(report.divergence.unwrap().kind, DivergenceKind::CfgMismatch { .. }));
+
+    // A tampered record is reported with its entry and intent.
+    let mut tampered = journal.clone();
+    tampered.entries[1].actions[0].escalation = Escalation::CritiquePass;
+    let d = replay(&ArbiterSupervisor::new(1, fresh.cfg().clone()), &builder, &tampered).divergence.unwrap();
+    assert_eq!((d.entry, d.intent_id.as_deref()), (1, Some("intent-1")));
+    assert_eq!(d.kind, DivergenceKind::Escalation { recorded: Escalation::CritiquePass, actual: Escalation::None });
+    let mut tampered = journal.clone();
+    tampered.entries[0].actions[1].cohort = Some("variant".to_string());
+    let d = replay(&ArbiterSupervisor::new(1, fresh.cfg().clone()), &builder, &tampered).divergence.unwrap();
+    assert_eq!((d.entry, d.intent_id.as_deref()), (0, Some("intent-2")));
+    assert_eq!(d.kind, DivergenceKind::Cohort { recorded: Some("variant".to_string()), actual: None });
+    let rep = FreezeFlags { rep_3p: true, ..FreezeFlags::default() };
+    let mut tampered = journal.clone();
+    tampered.entries[0].actions[1].source_flags.insert(0, ("asr".to_string(), rep));
+    assert_eq!(Journal::decode(&tampered.encode()), Ok(tampered.clone()));
+    let d = replay(&ArbiterSupervisor::new(1, fresh.cfg().clone()), &builder, &tampered).divergence.unwrap();
+    assert_eq!(d.kind, DivergenceKind::SourceFlags { source_id: "asr".to_string(), recorded: Some(rep), actual: None });
+}
+
+#[test]
+fn shadow_cfg_reports_disagreements() {
+    let builder = BasicEvidenceBuilder::default();
+    let events = vec![
+        SignalEvent::new("q1", "llm", "decoder").with_scalar("entropy", 2.2).with_scalar("cosine", 0.9),
+        SignalEvent::new("q2", "llm", "decoder").with_scalar("entropy", 3.0).with_scalar("cosine", 0.9),
+        SignalEvent::new("q3", "llm", "decoder").with_scalar("entropy", 1.0).with_scalar("cosine", 0.9),
+    ];
+    let plain = ArbiterSupervisor::new(2, ArbiterCfg::default());
+    let mut sup = ArbiterSupervisor::new(2, ArbiterCfg::default());
+    sup.set_shadow_cfg(ArbiterCfg { tau_e: 2.0, ..ArbiterCfg::default() });
+
+    // Live actions are untouched (and untraced) by the shadow.
+    let live = sup.ingest(&builder, &events);
+    let expected = plain.ingest(&builder, &events);
+    let esc = |out: &[nsc_arbiter_supervisor::ActionEvent]| out.iter().map(|a| a.escalation).collect::<Vec<_>>();
+    assert_eq!(esc(&live), esc(&expected));
+    assert!(live.iter().all(|a| a.trace.is_none()));
+
+    let reports = sup.take_shadow_reports();
+    assert_eq!(reports.len(), 1);
+    assert_eq!((reports[0].now, reports[0].decisions), (1, 3));
+    let diff = &reports[0].diffs[..];
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].intent_id, "q1");
+    assert_eq!((diff[0].live.escalation, diff[0].shadow.escalation), (Escalation::None, Escalation::CritiquePass));
+    assert!(diff[0].shadow.get(Predicate::HiEntropy).unwrap().fired);
+    assert!(sup.take_shadow_reports().is_empty());
+
+    // Batches where both agree are only counted.
+    sup.ingest(&builder, &events[1..]);
+    assert!(sup.take_shadow_reports().is_empty());
+
+    assert_eq!(
+        sup.shadow_stats(),
+        Some(ShadowStats { ticks: 2, decisions: 5, agreed: 4, shadow_only: 1, ..ShadowStats::default() })
+    );
+    assert_eq!(sup.clear_shadow().map(|s| s.disagreed()), Some(1));
+    assert!(sup.take_shadow_reports().is_empty());
+}
+
+#[test]
+fn cohorts_are_hashed_and_pinned() {
+    let builder = BasicEvidenceBuilder::default();
+    let ids: Vec<String> = (0..1000).map(|i| format!("q{i}")).collect();
+    let events: Vec<SignalEvent<'_>> = ids
+        .iter()
+        .map(|id| SignalEvent::new(id, "llm", "decoder").with_scalar("entropy", 3.0).with_scalar("cosine", 0.9))
+        .collect();
+    let strict = ArbiterCfg { tau_e: 9.0, ..ArbiterCfg::default() };
+
+    let mut sup = ArbiterSupervisor::new(4, ArbiterCfg::default());
+    sup.set_cohorts(Cohorts::new("exp-1").with_variant("strict", 0.1, strict));
+    sup.set_cfg_override("q0", ArbiterCfg::default());
+    let out = sup.ingest(&builder, &events);
+
+    // Variant intents use the variant cfg, everyone else the base cfg.
+    let strict_ids: Vec<&str> =
+        out.iter().filter(|a| a.cohort.as_deref() == Some("strict")).map(|a| a.intent_id.as_str()).collect();
+    assert!((70..=130).contains(&strict_ids.len()), "{}", strict_ids.len());
+    for a in &out {
+        let expected = if a.cohort.as_deref() == Some("strict") && a.intent_id != "q0" {
+            Escalation::None
+        } else {
+            Escalation::CritiquePass
+        };
+        assert_eq!(a.escalation, expected, "{}", a.intent_id);
+    }
+    assert!(out.iter().all(|a| a.cohort.is_some()));
+    assert_eq!(sup.cohort_of(strict_ids[0]).as_deref(), Some("strict"));
+
+    // Pins survive a restore even when the salt changes; new intents use the new salt.
+    let snap = sup.snapshot_full();
+    assert_eq!(snap.state.cohorts.len(), 1000);
+    let mut restored = ArbiterSupervisor::restore_full(snap).unwrap();
+    let reshuffled = Cohorts { salt: "exp-2".to_string(), ..restored.cohorts().unwrap().clone() };
+    restored.set_cohorts(reshuffled.clone());
+    for a in &out {
+        assert_eq!(restored.cohort_of(&a.intent_id), a.cohort);
+    }
+    let fresh = (0..1000).map(|i| format!("new{i}")).find(|id| reshuffled.assign(id) == "strict").unwrap();
+    assert_eq!(restored.cohort_of(&fresh).as_deref(), Some("strict"));
+
+    // Cleared intents lose their pin; without cohorts nothing is reported.
+    let moved = out.iter().find(|a| reshuffled.assign(&a.intent_id) != a.cohort.as_deref().unwrap()).unwrap();
+    restored.clear_intent(&moved.intent_id);
+    assert_ne!(restored.cohort_of(&moved.intent_id), moved.cohort);
+    restored.clear_cohorts();
+    assert_eq!(restored.cohort_of("q1"), None);
+    assert!(restored.ingest(&builder, &events[..1])[0].cohort.is_none());
+    assert_eq!(Cohorts::new("s").assign("q1"), CONTROL_COHORT);
+}
+
+#[test]
+fn cross_tick_repeats_are_remembered() {
+    let builder = BasicEvidenceBuilder::default();
+    let para = "The quarterly report shows revenue grew in every region, led by strong demand overseas.";
+    let edited = "The quarterly report shows revenue grew in every region, led by strong demand abroad.";
+    let other = "Shipping delays eased after the port reopened and carriers added capacity this month.";
+    let ev = |text: &'static str| {
+        SignalEvent::new("q", "llm", "decoder").with_scalar("entropy", 3.0).with_scalar("cosine", 0.9).with_text(text)
+    };
+    let repeat = |a: &ActionEvent| a.freeze_flags.unwrap().cross_tick_repeat;
+
+    // Off by default: each tick looks clean on its own.
+    let sup = ArbiterSupervisor::new(2, ArbiterCfg::default());
+    for _ in 0..3 {
+        assert!(!repeat(&sup.ingest(&builder, &[ev(para)])[0]));
+    }
+
+    let mut cfg = ArbiterCfg { tau_rep: 2, ..ArbiterCfg::default() };
+    cfg.freeze.recent_texts = 2;
+    cfg.freeze.near_duplicate_bits = 12;
+    let sup = ArbiterSupervisor::new(2, cfg.clone());
+    let a = &sup.ingest(&builder, &[ev(para)])[0];
+    assert!(!repeat(a) && !a.freeze_flags.unwrap().rep_3p);
+    assert!(repeat(&sup.ingest(&builder, &[ev(edited)])[0]));
+    let a = &sup.ingest(&builder, &[ev(para)])[0];
+    assert!(repeat(a));
+    assert_eq!(a.escalation, Escalation::Pause { reason: PauseReason::Repetition });
+    assert!(!repeat(&sup.ingest(&builder, &[ev(other)])[0]));
+
+    // Memories travel with snapshots and deltas, and go with the intent.
+    let restored = ArbiterSupervisor::new(1, cfg.clone());
+    restored.restore(sup.snapshot());
+    assert_eq!(restored.snapshot().text_memory, sup.snapshot().text_memory);
+    assert_eq!(sup.snapshot().text_memory[0].2.signatures.len(), 2);
+    assert!(repeat(&restored.ingest(&builder, &[ev(other)])[0]));
+    let replica = ArbiterSupervisor::new(3, cfg.clone());
+    replica.apply_delta(restored.snapshot_delta(CheckpointToken::ORIGIN));
+    assert_eq!(replica.snapshot().text_memory, restored.snapshot().text_memory);
+    replica.clear_intent("q");
+    assert!(replica.snapshot().text_memory.is_empty());
+    assert!(!repeat(&replica.ingest(&builder, &[ev(other)])[0]));
+
+    // An override's `freeze` settings apply to its intent only.
+    let mut sup = ArbiterSupervisor::new(2, ArbiterCfg::default());
+    sup.set_cfg_override("q", cfg);
+    let r = SignalEvent::new("r", "llm", "decoder").with_scalar("entropy", 3.0).with_text(para);
+    sup.ingest(&builder, &[ev(para), r.clone()]);
+    let out = sup.ingest(&builder, &[ev(para), r]);
+    assert!(repeat(&out[0]) && !repeat(&out[1]));
+}
+
+#[test]
+fn freeze_flags_are_tracked_per_source() {
+    const STALL: &str = "abababababababababab";
+    const CLEAN: &str = "the quick brown fox";
+    let builder = BasicEvidenceBuilder::default();
+    let tick = |sup: &ArbiterSupervisor, stt: &'static str, llm: &'static str| {
+        let ev = |source, text| SignalEvent::new("q", source, "decoder").with_scalar("entropy", 3.0).with_text(text);
+        sup.ingest(&builder, &[ev("stt", stt), ev("llm", llm)]).remove(0)
+    };
+    let stall = FreezeFlags { stall: true, ..FreezeFlags::default() };
+    let paused = Escalation::Pause { reason: PauseReason::Stall };
+    let cfg = ArbiterCfg { tau_stall: 2, ..ArbiterCfg::default() };
+
+    // A stall from each source once is not two stalls of the intent.
+    let sup = ArbiterSupervisor::new(1, cfg.clone());
+    let a = tick(&sup, STALL, CLEAN);
+    assert_eq!(a.source_flags, vec![("stt".to_string(), stall)]);
+    assert_eq!(a.freeze_flags, Some(stall));
+    let a = tick(&sup, CLEAN, STALL);
+    assert_eq!(a.source_flags, vec![("llm".to_string(), stall)]);
+    assert_eq!(a.escalation, Escalation::CritiquePass);
+    let state = &sup.snapshot().states[0].1;
+    assert_eq!((state.hyst_stall, state.source_hyst["stt"].stall, state.source_hyst["llm"].stall), (1.0, 1.0, 1.0));
+    assert_eq!(tick(&sup, CLEAN, STALL).escalation, paused);
+    assert!(sup.snapshot().states[0].1.source_hyst.is_empty(), "the pause consumes every source's counters");
+
+    // An ineligible source's text is not checked at all.
+    let mut sup = ArbiterSupervisor::new(1, cfg);
+    sup.set_source_profile("stt", SourceProfile::new(1.0, 0.5, 1.0).with_freeze_eligible(false));
+    for _ in 0..3 {
+        let a = tick(&sup, STALL, CLEAN);
+        assert_eq!(a.escalation, Escalation::CritiquePass);
+        assert!(a.source_flags.is_empty());
+        assert_eq!(a.freeze_flags, Some(FreezeFlags::default()));
+    }
+    assert!(!sup.source_profile("stt").unwrap().freeze_eligible);
+    assert!(sup.source_profile("llm").is_none());
+
+    // An override's `freeze` settings detect for its intent only.
+    let mut sup = ArbiterSupervisor::new(2, ArbiterCfg::default());
+    let mut cfg = ArbiterCfg::default();
+    cfg.freeze.ai_phrases = vec!["als ki-sprachmodell".to_string()];
+    sup.set_cfg_override("q", cfg);
+    let ev = |intent| SignalEvent::new(intent, "llm", "decoder").with_text("Als KI-Sprachmodell kann ich das nicht.");
+    let out = sup.ingest(&builder, &[ev("q"), ev("r")]);
+    assert!(out[0].freeze_flags.unwrap().ai_tell);
+    assert!(!out[1].freeze_flags.unwrap().ai_tell);
+}
+
+#[test]
+fn quiet_ticks_reset_each_source_by_its_own_flags() {
+    const STALL: &str = "abababababababababab";
+    const CLEAN: &str = "the quick brown fox";
+    let builder = BasicEvidenceBuilder::default();
+    let tick = |sup: &ArbiterSupervisor, stt: &'static str, llm: &'static str| {
+        let ev = |source, text| SignalEvent::new("q", source, "decoder").with_scalar("cosine", 1.0).with_text(text);
+        sup.ingest(&builder, &[ev("stt", stt), ev("llm", llm)]).remove(0).escalation
+    };
+    let sup = ArbiterSupervisor::new(1, ArbiterCfg { tau_stall: 2, ..ArbiterCfg::default() });
+
+    // Another source's stall does not keep stt's count alive across a clean stt tick.
+    assert_eq!(tick(&sup, STALL, CLEAN), Escalation::None);
+    assert_eq!(tick(&sup, CLEAN, STALL), Escalation::None);
+    assert_eq!(tick(&sup, STALL, CLEAN), Escalation::None);
+    let state = &sup.snapshot().states[0].1;
+    assert_eq!((state.hyst_stall, state.source_hyst["stt"].stall), (1.0, 1.0));
+    assert!(!state.source_hyst.contains_key("llm"));
+
+    // A source stalling on consecutive quiet ticks still pauses.
+    assert_eq!(tick(&sup, STALL, CLEAN), Escalation::Pause { reason: PauseReason::Stall });
+}